@@ -0,0 +1,192 @@
+use std::{ fmt, str::FromStr };
+
+use anyhow::{ anyhow, Result };
+use url::Url;
+
+/// Kinds of Bitbucket issues, the other labels of Bitbucket issues are components
+static BITBUCKET_KINDS: &[&str] = &[ "bug", "enhancement", "proposal", "task" ];
+
+/// Forge a repository is synchronised from
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Backend {
+    Github,
+    Gitlab,
+    /// Also covers Forgejo, which shares the Gitea API
+    Gitea,
+    /// Bitbucket Cloud only, Bitbucket Server has no issue tracker
+    Bitbucket,
+    /// todo.sr.ht, owners carry their ~ prefix
+    Sourcehut,
+    /// Imported from Jira, owner is the site host and name the project key
+    Jira
+}
+
+impl Backend {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Github => "github",
+            Self::Gitlab => "gitlab",
+            Self::Gitea => "gitea",
+            Self::Bitbucket => "bitbucket",
+            Self::Sourcehut => "sourcehut",
+            Self::Jira => "jira"
+        }
+    }
+
+    /// Web root of the public instance
+    pub fn default_base_url(&self) -> Url {
+        let url = match self {
+            Self::Github => "https://github.com",
+            Self::Gitlab => "https://gitlab.com",
+            Self::Gitea => "https://codeberg.org",
+            Self::Bitbucket => "https://bitbucket.org",
+            Self::Sourcehut => "https://todo.sr.ht",
+            Self::Jira => "https://jira.atlassian.com"
+        };
+        Url::parse(url).expect("invalid default base url")
+    }
+
+    /// Whether --base-url can point to instances other than the public one
+    pub fn is_self_hostable(&self) -> bool {
+        match self {
+            Self::Github | Self::Bitbucket => false,
+            Self::Gitlab | Self::Gitea | Self::Sourcehut | Self::Jira => true
+        }
+    }
+
+    /// Web page listing the issues of <owner>/<name> carrying `label`
+    pub fn label_url(&self, base: &Url, owner: &str, name: &str, label: &str) -> Url {
+        if let Self::Jira = self {
+            let mut url = with_segments(base, &[ "issues", "" ]);
+            url.query_pairs_mut()
+                .append_pair("jql", &format!("project = \"{}\" AND labels = \"{}\"", name, label));
+            return url
+        }
+
+        let mut url = base.clone();
+        {
+            let mut segments = url.path_segments_mut()
+                .expect("base url cannot be a base");
+            segments.pop_if_empty()
+                .extend(owner.split('/'))
+                .push(name);
+            match self {
+                Self::Github => { segments.push("labels").push(label); },
+                Self::Gitlab => { segments.push("-").push("issues"); },
+                // Gitea filters issues by label id, which isn't stored
+                Self::Gitea => { segments.push("labels"); },
+                // Bitbucket has no labels, they are derived from kind and component, filtered below
+                Self::Bitbucket => { segments.push("issues"); },
+                Self::Sourcehut | Self::Jira => ()
+            }
+        }
+        match self {
+            Self::Gitlab => { url.query_pairs_mut().append_pair("label_name[]", label); },
+            Self::Sourcehut => { url.query_pairs_mut().append_pair("search", &format!("label:\"{}\"", label)); },
+            Self::Bitbucket => {
+                let filter = if BITBUCKET_KINDS.contains(&label) { "kind" } else { "component" };
+                url.query_pairs_mut().append_pair(filter, label);
+            },
+            _ => ()
+        }
+        url
+    }
+
+    /// Web page of <owner>/<name>, ending in / so relative URLs resolve below it
+    pub fn repository_url(&self, base: &Url, owner: &str, name: &str) -> Url {
+        if let Self::Jira = self {
+            return with_segments(base, &[ "browse", name, "" ])
+        }
+
+        let mut url = base.clone();
+        url.path_segments_mut()
+            .expect("base url cannot be a base")
+            .pop_if_empty()
+            .extend(owner.split('/'))
+            .push(name)
+            .push("");
+        url
+    }
+
+    /// Web page listing all issues of <owner>/<name>
+    pub fn issues_url(&self, base: &Url, owner: &str, name: &str) -> Url {
+        if let Self::Jira = self {
+            let mut url = with_segments(base, &[ "issues", "" ]);
+            url.query_pairs_mut()
+                .append_pair("jql", &format!("project = \"{}\"", name));
+            return url
+        }
+
+        let mut url = base.clone();
+        {
+            let mut segments = url.path_segments_mut()
+                .expect("base url cannot be a base");
+            segments.pop_if_empty()
+                .extend(owner.split('/'))
+                .push(name);
+            match self {
+                Self::Gitlab => { segments.push("-").push("issues"); },
+                Self::Github | Self::Gitea | Self::Bitbucket => { segments.push("issues"); },
+                Self::Sourcehut | Self::Jira => ()
+            }
+        }
+        url
+    }
+
+    /// Avatar image of the user or organization `owner`, if the forge serves it under a fixed URL
+    pub fn avatar_url(&self, base: &Url, owner: &str) -> Option<Url> {
+        match self {
+            Self::Github => base.join(&format!("/{}.png", owner)).ok(),
+            _ => None
+        }
+    }
+
+    /// Profile page of `login`
+    pub fn user_url(&self, base: &Url, login: &str) -> Url {
+        // Jira Cloud only exposes display names, which don't address a profile
+        if let Self::Jira = self { return base.clone() }
+
+        let mut url = base.clone();
+        url.path_segments_mut()
+            .expect("base url cannot be a base")
+            .pop_if_empty()
+            .push(login);
+        url
+    }
+}
+
+/// `base` with `segments` appended to its path, keeping a context path like https://host/jira/
+pub fn with_segments(base: &Url, segments: &[&str]) -> Url {
+    let mut url = base.clone();
+    url.path_segments_mut()
+        .expect("base url cannot be a base")
+        .pop_if_empty()
+        .extend(segments);
+    url
+}
+
+impl Default for Backend {
+    fn default() -> Self { Self::Github }
+}
+
+impl FromStr for Backend {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "github" => Ok(Self::Github),
+            "gitlab" => Ok(Self::Gitlab),
+            "gitea" | "forgejo" => Ok(Self::Gitea),
+            "bitbucket" => Ok(Self::Bitbucket),
+            "sourcehut" | "srht" => Ok(Self::Sourcehut),
+            "jira" => Ok(Self::Jira),
+            _ => Err(anyhow!("unknown backend '{}', expected one of: github, gitlab, gitea, bitbucket, sourcehut, jira", s))
+        }
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
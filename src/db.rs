@@ -0,0 +1,591 @@
+use std::{
+    fs,
+    path::{ Path, PathBuf }
+};
+
+use sqlx::{ prelude::*, SqlitePool, Transaction, pool::PoolConnection };
+use structopt::StructOpt;
+use anyhow::{ anyhow, Result, Context };
+use tracing::info;
+
+use crate::{ Conn, search, query::{ self, issues::IssueState } };
+
+static JOURNAL_MODES: &[&str] = &[ "delete", "truncate", "persist", "memory", "wal", "off" ];
+
+#[derive(StructOpt)]
+pub struct DbOpts {
+    /// SQLite database holding the synchronised issues
+    #[structopt(long = "db", env = "GH_ISSUE_FEED_DB", default_value = "./issues.sqlite")]
+    pub path: PathBuf,
+    /// Keep every repository in its own database <data-dir>/<owner>/<name>.sqlite instead
+    #[structopt(long, env = "GH_ISSUE_FEED_DATA_DIR")]
+    pub data_dir: Option<PathBuf>,
+    /// SQLite journal mode. WAL lets generate read while a sync is writing
+    #[structopt(long, default_value = "wal", possible_values = JOURNAL_MODES)]
+    pub journal_mode: String,
+    /// Milliseconds to wait for another process to release the database before failing
+    #[structopt(long, default_value = "5000")]
+    pub busy_timeout: u32,
+    /// Store issue bodies as plain text instead of zstd-compressed
+    #[structopt(long)]
+    pub uncompressed_bodies: bool
+}
+
+pub struct Database {
+    pool: SqlitePool,
+    pragmas: String
+}
+
+impl Database {
+    /// With `read_only`, every statement that would change the database fails.
+    /// sqlx always opens files for writing, so this is enforced with `PRAGMA query_only`
+    pub async fn open(opts: &DbOpts, path: &Path, read_only: bool) -> Result<Self> {
+        check_location(path)?;
+        // Every connection to :memory: gets a database of its own, so a single one is kept open
+        let pool = if is_in_memory(path) {
+            SqlitePool::builder()
+                .min_size(1)
+                .max_size(1)
+                .idle_timeout(None)
+                .max_lifetime(None)
+                .build("sqlite::memory:")
+                .await?
+        } else {
+            SqlitePool::new(&format!("sqlite:{}", path.display()))
+                .await
+                .with_context(|| format!("Failed to open database {}", path.display()))?
+        };
+
+        // Pragmas are per connection, but the pool offers no hook for new connections
+        let pragmas = if read_only {
+            format!(r#"
+                PRAGMA query_only = ON;
+                PRAGMA busy_timeout = {};
+            "#, opts.busy_timeout)
+        } else {
+            format!(r#"
+                PRAGMA foreign_keys = ON;
+                PRAGMA synchronous = OFF;
+                PRAGMA journal_mode = {};
+                PRAGMA busy_timeout = {};
+            "#, opts.journal_mode, opts.busy_timeout)
+        };
+
+        Ok(Database { pool, pragmas })
+    }
+
+    pub async fn acquire(&self) -> Result<PoolConnection<Conn>> {
+        let mut conn = self.pool.acquire().await?;
+        sqlx::query(&self.pragmas)
+            .execute(&mut *conn)
+            .await
+            .context("Failed to configure database connection")?;
+        Ok(conn)
+    }
+
+    /// Most connections the pool opens at once
+    pub fn max_connections(&self) -> usize {
+        self.pool.max_size() as usize
+    }
+
+    pub async fn begin(&self) -> Result<Transaction<PoolConnection<Conn>>> {
+        Ok(self.acquire().await?.begin().await?)
+    }
+}
+
+/// Split `body` into the values of the body and body_zstd columns, compressed unless --uncompressed-bodies
+pub fn encode_body(body: &str, compress: bool) -> Result<(&str, Option<Vec<u8>>)> {
+    if !compress {
+        return Ok((body, None))
+    }
+    Ok(("", Some(zstd::encode_all(body.as_bytes(), 0)?)))
+}
+
+/// Inverse of `encode_body`, for any row regardless of how it was stored
+pub fn decode_body(body: String, body_zstd: Option<Vec<u8>>) -> Result<String> {
+    match body_zstd {
+        Some(compressed) => Ok(String::from_utf8(zstd::decode_all(&compressed[..])?)?),
+        None => Ok(body)
+    }
+}
+
+async fn compress_stored_bodies(conn: &mut Conn) -> Result<()> {
+    let bodies = sqlx::query_as::<_, (i64, i64, String)>(
+        "SELECT repo, number, body FROM issues WHERE body_zstd IS NULL"
+    ).fetch_all(&mut *conn)
+     .await?;
+
+    info!("compressing {} issue bodies", bodies.len());
+    for (repo, number, body) in bodies {
+        let (body, body_zstd) = encode_body(&body, true)?;
+        sqlx::query(
+            "UPDATE issues SET body = ?, body_zstd = ? WHERE repo = ? AND number = ?"
+        ).bind(body).bind(body_zstd).bind(repo).bind(number)
+         .execute(&mut *conn)
+         .await?;
+    }
+    Ok(())
+}
+
+/// Before schema 13, GitHub repositories imported with `import gh` or `gharchive` kept their Markdown
+/// bodies unmarked among the HTML of synced ones. GitHub renders bodies to HTML starting with a tag,
+/// so other non-empty bodies of GitHub repositories are marked as Markdown
+async fn mark_markdown_bodies(conn: &mut Conn) -> Result<()> {
+    let bodies = sqlx::query_as::<_, (i64, i64, String, Option<Vec<u8>>)>(
+        "SELECT repo, number, body, body_zstd FROM issues
+         WHERE body_markdown = 0 AND repo IN (SELECT id FROM repositories WHERE backend = 'github')"
+    ).fetch_all(&mut *conn)
+     .await?;
+
+    let mut marked = Vec::new();
+    for (repo, number, body, body_zstd) in bodies {
+        let body = decode_body(body, body_zstd)?;
+        let body = body.trim_start();
+        if !body.is_empty() && !body.starts_with('<') {
+            marked.push((repo, number));
+        }
+    }
+
+    info!("marking {} imported issue bodies as Markdown", marked.len());
+    for (repo, number) in marked {
+        sqlx::query("UPDATE issues SET body_markdown = 1 WHERE repo = ? AND number = ?")
+            .bind(repo).bind(number)
+            .execute(&mut *conn)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Ordered schema changes. A database has applied the first `PRAGMA user_version`
+/// of them, so applied migrations must never change; append new ones instead.
+static MIGRATIONS: &[&str] = &[
+    // 1: initial schema, databases created before migrations existed are at this point
+    r#"
+        CREATE TABLE IF NOT EXISTS repositories(
+            id integer PRIMARY KEY,
+            owner text, name text,
+            UNIQUE (owner, name)
+        );
+
+        CREATE TABLE IF NOT EXISTS issues(
+            repo integer REFERENCES repositories,
+            number integer,
+            state integer, title text, body text,
+            user_login text,
+            html_url text,
+            updated_at integer,
+            PRIMARY KEY (repo, number)
+        );
+        CREATE INDEX IF NOT EXISTS issues_state ON issues (repo, number, state);
+
+        CREATE TABLE IF NOT EXISTS labels(
+            id integer PRIMARY KEY,
+            repo integer REFERENCES repositories,
+            name text,
+            UNIQUE (repo, name)
+        );
+
+        CREATE TABLE IF NOT EXISTS is_labeled(
+            repo integer, issue integer,
+            label integer RFERENCES labels,
+            PRIMARY KEY (repo, issue, label),
+            FOREIGN KEY (repo, issue) REFERENCES issues
+        );
+    "#,
+    // 2: forge backends
+    r#"
+        ALTER TABLE repositories ADD COLUMN backend text NOT NULL DEFAULT 'github';
+        ALTER TABLE repositories ADD COLUMN base_url text;
+    "#,
+    // 3: term index over issue titles and bodies, see search.rs.
+    // FTS5 is out of reach, sqlx prepares every statement with SQLITE_PREPARE_NO_VTAB
+    r#"
+        CREATE TABLE issue_terms(
+            repo integer, issue integer,
+            term text,
+            PRIMARY KEY (term, repo, issue)
+        ) WITHOUT ROWID;
+        CREATE INDEX issue_terms_issue ON issue_terms (repo, issue);
+
+        CREATE TRIGGER issue_terms_delete AFTER DELETE ON issues BEGIN
+            DELETE FROM issue_terms WHERE repo = old.repo AND issue = old.number;
+        END;
+    "#,
+    // 4: enforce the label reference of is_labeled, which was misspelt as RFERENCES.
+    // Labelings that would violate it are dropped
+    r#"
+        CREATE TABLE is_labeled_new(
+            repo integer, issue integer,
+            label integer REFERENCES labels,
+            PRIMARY KEY (repo, issue, label),
+            FOREIGN KEY (repo, issue) REFERENCES issues
+        );
+        INSERT INTO is_labeled_new (repo, issue, label)
+            SELECT repo, issue, label FROM is_labeled
+            WHERE label IN (SELECT id FROM labels)
+              AND (repo, issue) IN (SELECT repo, number FROM issues);
+        DROP TABLE is_labeled;
+        ALTER TABLE is_labeled_new RENAME TO is_labeled;
+    "#,
+    // 5: zstd-compressed bodies. body is left empty for issues stored with compression
+    r#"
+        ALTER TABLE issues ADD COLUMN body_zstd blob;
+    "#,
+    // 6: authors. Their html_url is the profile page, so equal logins on different forges stay apart
+    r#"
+        CREATE TABLE users(
+            id integer PRIMARY KEY,
+            login text NOT NULL,
+            html_url text NOT NULL,
+            avatar_url text,
+            UNIQUE (html_url, login)
+        );
+        ALTER TABLE issues ADD COLUMN user integer REFERENCES users;
+    "#,
+    // 7: label names treated as another label of the same meaning, in every repository
+    r#"
+        CREATE TABLE label_aliases(
+            alias text PRIMARY KEY,
+            label text NOT NULL
+        );
+    "#,
+    // 8: indices for feed generation and incremental sync
+    r#"
+        CREATE INDEX is_labeled_label ON is_labeled (repo, label, issue);
+        CREATE INDEX label_aliases_label ON label_aliases (label);
+        CREATE INDEX issues_updated_at ON issues (repo, updated_at);
+    "#,
+    // 9: start of the last successful sync, NULL until the next one
+    r#"
+        ALTER TABLE repositories ADD COLUMN last_synced_at integer;
+    "#,
+    // 10: assignees
+    r#"
+        CREATE TABLE is_assigned(
+            repo integer, issue integer,
+            user integer NOT NULL REFERENCES users,
+            PRIMARY KEY (repo, issue, user),
+            FOREIGN KEY (repo, issue) REFERENCES issues
+        );
+        CREATE INDEX is_assigned_user ON is_assigned (repo, user, issue);
+    "#,
+    // 11: creation time and reaction count, NULL where the forge doesn't report them
+    r#"
+        ALTER TABLE issues ADD COLUMN created_at integer;
+        ALTER TABLE issues ADD COLUMN reactions integer;
+    "#,
+    // 12: label colors as hex RGB without leading #
+    r#"
+        ALTER TABLE labels ADD COLUMN color text;
+    "#,
+    // 13: Markdown bodies, rendered by generate. GitHub and Jira store HTML, the other forges Markdown.
+    // Imported GitHub bodies are marked by mark_markdown_bodies
+    r#"
+        ALTER TABLE issues ADD COLUMN body_markdown integer NOT NULL DEFAULT 0;
+        UPDATE issues SET body_markdown=1
+        WHERE repo IN (SELECT id FROM repositories WHERE backend IN ('gitlab', 'gitea', 'bitbucket', 'sourcehut'));
+    "#,
+    // 14: most recent comments of issues, with bodies stored like those of issues
+    r#"
+        CREATE TABLE comments(
+            repo integer, issue integer,
+            author text NOT NULL,
+            created_at integer NOT NULL,
+            body text NOT NULL,
+            body_zstd blob,
+            body_markdown integer NOT NULL DEFAULT 0,
+            FOREIGN KEY (repo, issue) REFERENCES issues
+        );
+        CREATE INDEX comments_issue ON comments (repo, issue, created_at);
+    "#,
+    // 15: milestones, and the milestone and closing time of issues, NULL where the forge doesn't report them
+    r#"
+        CREATE TABLE milestones(
+            repo integer REFERENCES repositories,
+            number integer,
+            title text NOT NULL,
+            html_url text NOT NULL,
+            due_on integer,
+            closed integer NOT NULL DEFAULT 0,
+            PRIMARY KEY (repo, number)
+        );
+        ALTER TABLE issues ADD COLUMN milestone integer;
+        ALTER TABLE issues ADD COLUMN closed_at integer;
+    "#,
+    // 16: comment count of issues, NULL where the forge doesn't report it
+    r#"
+        ALTER TABLE issues ADD COLUMN comment_count integer;
+    "#,
+    // 17: start of the last successful sync restricted by --label, per set of labels
+    r#"
+        CREATE TABLE label_syncs(
+            repo integer REFERENCES repositories,
+            labels text NOT NULL,
+            last_synced_at integer NOT NULL,
+            PRIMARY KEY (repo, labels)
+        );
+    "#,
+    // 18: owner/name of repositories before their first rename, kept in tag: entry ids
+    r#"
+        ALTER TABLE repositories ADD COLUMN tag_name text;
+    "#
+];
+
+pub fn is_in_memory(path: &Path) -> bool {
+    path == Path::new(":memory:")
+}
+
+/// Reject database URLs, `--db` takes the path of an SQLite file
+fn check_location(path: &Path) -> Result<()> {
+    let location = path.to_string_lossy();
+    if location.contains("://") {
+        return Err(anyhow!("Unsupported database '{}': only paths to SQLite files are supported, \
+                            as the queries rely on the SQLite dialect", location));
+    }
+    Ok(())
+}
+
+/// Database of one repository within --data-dir. With `create`, its directory is created if needed,
+/// otherwise the database has to exist, so that mistyped names don't leave empty databases behind
+pub fn repository_path(data_dir: &Path, (owner, name): &(String, String), create: bool) -> Result<PathBuf> {
+    let directory = data_dir.join(owner);
+    let path = directory.join(format!("{}.sqlite", name));
+    if create {
+        fs::create_dir_all(&directory)
+            .with_context(|| format!("Failed to create {}", directory.display()))?;
+    } else if !path.exists() {
+        return Err(anyhow!("Unknown repository {}/{}, {} doesn't exist", owner, name, path.display()));
+    }
+    Ok(path)
+}
+
+/// Delete the database of a removed repository within --data-dir, and its directory if now empty
+pub fn remove_repository_database(path: &Path) -> Result<()> {
+    for suffix in &[ "", "-wal", "-shm" ] {
+        let file = PathBuf::from(format!("{}{}", path.display(), suffix));
+        match fs::remove_file(&file) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound =>
+                return Err(e).with_context(|| format!("Failed to delete {}", file.display())),
+            _ => ()
+        }
+    }
+    if let Some(directory) = path.parent() {
+        // Fails while other repositories of the owner remain
+        fs::remove_dir(directory).ok();
+    }
+    Ok(())
+}
+
+/// All databases within --data-dir. Owners may be nested groups, so the search is recursive
+pub fn find_databases(data_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut databases = Vec::new();
+    let mut directories = vec![ data_dir.to_owned() ];
+    while let Some(directory) = directories.pop() {
+        for entry in fs::read_dir(&directory)
+                .with_context(|| format!("Failed to read {}", directory.display()))? {
+            let path = entry?.path();
+            if path.is_dir() {
+                directories.push(path);
+            } else if path.extension().map_or(false, |ext| ext == "sqlite") {
+                databases.push(path);
+            }
+        }
+    }
+    databases.sort();
+    Ok(databases)
+}
+
+async fn user_version(conn: &mut Conn) -> Result<usize> {
+    // Read to completion, as fetch_one leaves the statement active, which makes
+    // DROP TABLE in migrations fail with "database table is locked"
+    let version = sqlx::query_as::<_, (i64,)>("PRAGMA user_version")
+        .fetch_all(conn)
+        .await?
+        .pop()
+        .map_or(0, |(version,)| version);
+    Ok(version as usize)
+}
+
+/// For read-only access, which can't migrate the schema
+pub async fn check_version(conn: &mut Conn) -> Result<()> {
+    let version = user_version(&mut *conn).await?;
+    if version != MIGRATIONS.len() {
+        return Err(anyhow!("Database schema version {} doesn't match supported version {}, \
+                            run `db migrate` with this version first", version, MIGRATIONS.len()));
+    }
+    Ok(())
+}
+
+/// Bring the schema up to date, applying each pending migration in its own transaction.
+/// Bodies stored before compression was introduced are compressed with `compress_bodies`
+pub async fn migrate(conn: &mut Conn, compress_bodies: bool) -> Result<()> {
+    let version = user_version(&mut *conn).await?;
+    if version > MIGRATIONS.len() {
+        return Err(anyhow!("Database schema version {} is newer than supported version {}",
+                                   version, MIGRATIONS.len()));
+    }
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        let target = index + 1;
+        info!("migrating database to schema version {}", target);
+
+        let applied = sqlx::query(&format!("BEGIN; {} PRAGMA user_version = {}; COMMIT;", migration, target))
+            .execute(&mut *conn)
+            .await;
+        if let Err(e) = applied {
+            sqlx::query("ROLLBACK").execute(&mut *conn).await.ok();
+            return Err(e).with_context(|| format!("Failed to migrate database to schema version {}", target));
+        }
+    }
+
+    // Some changes can't be expressed in SQL and are applied to existing rows
+    // once the schema is complete
+    if version < 5 && compress_bodies {
+        compress_stored_bodies(&mut *conn).await?;
+    }
+    // Before reindexing, which strips tags only from HTML bodies
+    if version < 13 {
+        mark_markdown_bodies(&mut *conn).await?;
+    }
+    if version < 3 {
+        search::reindex(&mut *conn).await?;
+    }
+    if version < 6 {
+        query::link_users(&mut *conn).await?;
+    }
+
+    Ok(())
+}
+
+/// Inconsistencies found by `check`, as the table and condition selecting the affected rows.
+/// Ordered so that deleting rows never leaves references to them behind
+static CHECKS: &[(&str, &str)] = &[
+    ("labelings of missing issues or labels",
+     "is_labeled WHERE repo NOT IN (SELECT id FROM repositories)
+        OR (repo, issue) NOT IN (SELECT repo, number FROM issues)
+        OR label IS NULL
+        OR label NOT IN (SELECT id FROM labels WHERE labels.repo = is_labeled.repo)"),
+    ("assignments of missing issues or users",
+     "is_assigned WHERE (repo, issue) NOT IN (SELECT repo, number FROM issues)
+        OR user NOT IN (SELECT id FROM users)"),
+    ("comments of missing issues",
+     "comments WHERE (repo, issue) NOT IN (SELECT repo, number FROM issues)"),
+    ("issues of missing repositories",
+     "issues WHERE repo IS NULL OR repo NOT IN (SELECT id FROM repositories)"),
+    ("milestones of missing repositories",
+     "milestones WHERE repo IS NULL OR repo NOT IN (SELECT id FROM repositories)"),
+    ("label-scoped syncs of missing repositories",
+     "label_syncs WHERE repo IS NULL OR repo NOT IN (SELECT id FROM repositories)"),
+    ("labels of missing repositories",
+     "labels WHERE repo IS NULL OR repo NOT IN (SELECT id FROM repositories)"),
+    ("indexed terms of missing issues",
+     "issue_terms WHERE (repo, issue) NOT IN (SELECT repo, number FROM issues)")
+];
+
+/// Verify the database file and the relations between tables.
+/// With `repair`, rows referring to missing data are deleted
+pub async fn check(conn: &mut Conn, repair: bool) -> Result<()> {
+    let mut problems = 0;
+
+    let integrity = sqlx::query_as::<_, (String,)>("PRAGMA integrity_check")
+        .fetch_all(&mut *conn)
+        .await?;
+    for (message,) in &integrity {
+        if message != "ok" {
+            println!("integrity: {}", message);
+            problems += 1;
+        }
+    }
+    if problems > 0 {
+        return Err(anyhow!("Database file is damaged, restore it from a backup or synchronise again"));
+    }
+
+    for (description, selection) in CHECKS {
+        let (count,) = sqlx::query_as::<_, (i64,)>(&format!("SELECT count(*) FROM {}", selection))
+            .fetch_one(&mut *conn)
+            .await?;
+        if count == 0 { continue }
+
+        if repair {
+            sqlx::query(&format!("DELETE FROM {}", selection))
+                .execute(&mut *conn)
+                .await?;
+            println!("deleted {} {}", count, description);
+        } else {
+            println!("found {} {}", count, description);
+            problems += 1;
+        }
+    }
+
+    // The state of these can't be guessed, they have to be fetched again
+    let invalid = sqlx::query_as::<_, (String, String, i64, i64)>(
+        "SELECT repositories.owner, repositories.name, issues.number, issues.state FROM issues
+         JOIN repositories ON issues.repo = repositories.id
+         WHERE issues.state NOT IN (?, ?)"
+    ).bind(IssueState::OPEN.to_integer()).bind(IssueState::CLOSED.to_integer())
+     .fetch_all(&mut *conn)
+     .await?;
+    for (owner, name, number, state) in &invalid {
+        println!("invalid state {} of {}/{}#{}", state, owner, name, number);
+        problems += 1;
+    }
+
+    if problems > invalid.len() {
+        Err(anyhow!("Found {} problems, run with --repair to delete inconsistent rows", problems))
+    } else if !invalid.is_empty() {
+        Err(anyhow!("Found issues with invalid states, remove and synchronise their repositories again"))
+    } else {
+        Ok(())
+    }
+}
+
+/// Delete issues closed before `before`. Where the forge doesn't report the close time,
+/// the last update is taken, as a closed issue is never updated before being closed.
+pub async fn prune_closed(conn: &mut Conn, before: i64) -> Result<u64> {
+    let closed = IssueState::CLOSED.to_integer();
+    sqlx::query(
+        "DELETE FROM is_labeled WHERE (repo, issue) IN
+            (SELECT repo, number FROM issues WHERE state = ? AND coalesce(closed_at, updated_at) < ?);
+         DELETE FROM is_assigned WHERE (repo, issue) IN
+            (SELECT repo, number FROM issues WHERE state = ? AND coalesce(closed_at, updated_at) < ?);
+         DELETE FROM comments WHERE (repo, issue) IN
+            (SELECT repo, number FROM issues WHERE state = ? AND coalesce(closed_at, updated_at) < ?)"
+    ).bind(closed).bind(before)
+     .bind(closed).bind(before)
+     .bind(closed).bind(before)
+     .execute(&mut *conn)
+     .await?;
+
+    Ok(sqlx::query(
+        "DELETE FROM issues WHERE state = ? AND coalesce(closed_at, updated_at) < ?"
+    ).bind(closed).bind(before)
+     .execute(&mut *conn)
+     .await?)
+}
+
+/// Write a consistent snapshot of the database to the new file `target`.
+/// Other connections may keep writing meanwhile
+pub async fn backup(conn: &mut Conn, target: &Path) -> Result<()> {
+    if target.exists() {
+        return Err(anyhow!("Backup target {} already exists", target.display()));
+    }
+    // The database is opened read-only, but query_only also rejects VACUUM INTO,
+    // although it leaves the database unchanged
+    sqlx::query("PRAGMA query_only = OFF")
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query("VACUUM INTO ?")
+        .bind(target.to_string_lossy().into_owned())
+        .execute(conn)
+        .await
+        .with_context(|| format!("Failed to back up database to {}", target.display()))?;
+    Ok(())
+}
+
+/// Rebuild the database file, returning the space of deleted rows to the file system
+pub async fn vacuum(conn: &mut Conn) -> Result<()> {
+    sqlx::query("VACUUM")
+        .execute(conn)
+        .await?;
+    Ok(())
+}
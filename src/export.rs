@@ -0,0 +1,172 @@
+use std::{
+    fs::File,
+    io::{ self, Write, BufWriter },
+    collections::HashMap
+};
+
+use sqlx::prelude::*;
+use anyhow::{ anyhow, Result, Context };
+use futures::StreamExt;
+use chrono::{ Utc, TimeZone };
+use tracing::info;
+
+use crate::{
+    parse_repo,
+    Conn, ExportOpts, db,
+    import::records::{ Record, CsvRecord },
+    query::{ find_repo_id, issues::IssueState }
+};
+
+pub static FORMATS: &[&str] = &[ "ndjson", "csv" ];
+
+/// Read all issues of `repo` with their labels, ordered by number
+async fn records(conn: &mut Conn, repo: &Repository) -> Result<Vec<Record>> {
+    let repo_name = format!("{}/{}", repo.owner, repo.name);
+    let (backend, base_url) = (&repo.backend, &repo.base_url);
+    let repo = repo.id;
+
+    let mut labels = HashMap::<i64, Vec<String>>::new();
+    let mut label_rows = sqlx::query_as::<_, (i64, String)>(
+        "SELECT is_labeled.issue, labels.name FROM is_labeled
+         JOIN labels ON is_labeled.label = labels.id
+         WHERE is_labeled.repo = ?
+         ORDER BY labels.name"
+    ).bind(repo)
+     .fetch(&mut *conn);
+    while let Some(row) = label_rows.next().await {
+        let (issue, label) = row?;
+        labels.entry(issue).or_default().push(label);
+    }
+    drop(label_rows);
+
+    let mut assignees = HashMap::<i64, Vec<String>>::new();
+    let mut assignee_rows = sqlx::query_as::<_, (i64, String)>(
+        "SELECT is_assigned.issue, users.login FROM is_assigned
+         JOIN users ON is_assigned.user = users.id
+         WHERE is_assigned.repo = ?
+         ORDER BY users.login"
+    ).bind(repo)
+     .fetch(&mut *conn);
+    while let Some(row) = assignee_rows.next().await {
+        let (issue, login) = row?;
+        assignees.entry(issue).or_default().push(login);
+    }
+    drop(assignee_rows);
+
+    let issues = sqlx::query_as::<_, Issue>(
+        "SELECT number, state, title, body, body_zstd, body_markdown, user_login, html_url, updated_at, created_at,
+                reactions
+         FROM issues
+         WHERE repo = ?
+         ORDER BY number"
+    ).bind(repo)
+     .fetch(&mut *conn)
+     .collect::<Vec<_>>()
+     .await;
+
+    issues.into_iter()
+        .map(|row| {
+            let Issue {
+                number, state, title, body, body_zstd, body_markdown, user_login, html_url, updated_at, created_at, reactions
+            } = row?;
+            let body = db::decode_body(body, body_zstd)?;
+            let state = IssueState::from_integer(state)
+                .and_then(|state| state.to_string())
+                .ok_or_else(|| anyhow!("Inconsistent database, invalid state of #{}", number))?;
+            Ok(Record {
+                number, title, body, state,
+                body_format: if body_markdown { Some(String::from("markdown")) } else { None },
+                updated_at: Utc.timestamp(updated_at, 0).to_rfc3339(),
+                created_at: created_at.map(|created_at| Utc.timestamp(created_at, 0).to_rfc3339()),
+                reactions,
+                author: Some(user_login),
+                url: Some(html_url).filter(|url| !url.is_empty()),
+                labels: labels.remove(&number).unwrap_or_default(),
+                assignees: assignees.remove(&number).unwrap_or_default(),
+                repo: Some(repo_name.clone()),
+                backend: Some(backend.clone()),
+                base_url: base_url.clone()
+            })
+        })
+        .collect()
+}
+
+#[derive(sqlx::FromRow)]
+struct Issue {
+    number: i64,
+    state: i64,
+    title: String,
+    body: String,
+    body_zstd: Option<Vec<u8>>,
+    body_markdown: bool,
+    user_login: String,
+    html_url: String,
+    updated_at: i64,
+    created_at: Option<i64>,
+    reactions: Option<i64>
+}
+
+#[derive(sqlx::FromRow)]
+struct Repository {
+    id: i64,
+    owner: String,
+    name: String,
+    backend: String,
+    base_url: Option<String>
+}
+
+pub async fn run(conn: &mut Conn, opts: ExportOpts) -> Result<()> {
+    let repos = match &opts.repo {
+        Some(repo) => {
+            let (ref owner, ref name) = parse_repo(repo)?;
+            let repo = find_repo_id(&mut *conn, owner, name).await?
+                .ok_or_else(|| anyhow!("Unknown repository {}", repo))?;
+            vec![ repo ]
+        },
+        None if opts.format == "csv" => return Err(anyhow!("CSV exports are limited to a single repository")),
+        None => sqlx::query_as::<_, (i64,)>("SELECT id FROM repositories ORDER BY owner, name")
+            .fetch_all(&mut *conn)
+            .await?
+            .into_iter()
+            .map(|(id,)| id)
+            .collect()
+    };
+
+    let mut records = Vec::new();
+    for id in repos {
+        let repo = sqlx::query_as::<_, Repository>(
+            "SELECT id, owner, name, backend, base_url FROM repositories WHERE id = ?"
+        ).bind(id)
+         .fetch_one(&mut *conn)
+         .await?;
+        let exported = self::records(&mut *conn, &repo).await?;
+        info!("exporting {} issues of {}/{}", exported.len(), repo.owner, repo.name);
+        records.extend(exported);
+    }
+
+    let out: Box<dyn Write> = match &opts.out {
+        Some(path) => Box::new(File::create(path)
+            .with_context(|| format!("Failed to create {}", path.display()))?),
+        None => Box::new(io::stdout())
+    };
+    let mut out = BufWriter::new(out);
+
+    match &*opts.format {
+        "csv" => {
+            let mut writer = csv::Writer::from_writer(out);
+            for record in records {
+                writer.serialize(CsvRecord::from(record))?;
+            }
+            writer.flush()?;
+        },
+        _ => {
+            for record in records {
+                serde_json::to_writer(&mut out, &record)?;
+                out.write_all(b"\n")?;
+            }
+            out.flush()?;
+        }
+    }
+
+    Ok(())
+}
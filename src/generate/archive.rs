@@ -0,0 +1,58 @@
+use std::{ fs, path::Path };
+
+use anyhow::{ anyhow, Result };
+use flate2::write::GzEncoder;
+
+use super::write_atomic;
+
+/// Add the files below `directory` to `archive` under `prefix`, sorted for reproducible archives.
+/// Hidden files, like the fingerprints and unfinished temporary files, are left out
+fn append_directory<W: std::io::Write>(archive: &mut tar::Builder<W>, directory: &Path, prefix: &Path) -> Result<()> {
+    let mut entries = fs::read_dir(directory)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        if entry.file_name().to_string_lossy().starts_with('.') { continue }
+        let path = entry.path();
+        let name = prefix.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            archive.append_dir(&name, &path)?;
+            append_directory(archive, &path, &name)?;
+        } else {
+            archive.append_path_with_name(&path, &name)?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether `archive` names a gzip-compressed tarball, failing on unsupported extensions
+pub fn is_gzip(archive: &Path) -> Result<bool> {
+    let name = archive.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        Ok(true)
+    } else if name.ends_with(".tar") {
+        Ok(false)
+    } else {
+        Err(anyhow!("Unsupported archive {}, use .tar, .tar.gz or .tgz", archive.display()))
+    }
+}
+
+/// Pack the output directory `out_path` into the tar archive `archive`,
+/// gzip-compressed if its name ends in .tar.gz or .tgz
+pub fn write(out_path: &Path, archive: &Path) -> Result<()> {
+    let gzip = is_gzip(archive)?;
+    write_atomic(archive, |out| {
+        if gzip {
+            let mut builder = tar::Builder::new(GzEncoder::new(out, flate2::Compression::default()));
+            append_directory(&mut builder, out_path, Path::new(""))?;
+            builder.into_inner()?.finish()?;
+        } else {
+            let mut builder = tar::Builder::new(out);
+            append_directory(&mut builder, out_path, Path::new(""))?;
+            builder.finish()?;
+        }
+        Ok(())
+    })
+}
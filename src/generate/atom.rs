@@ -0,0 +1,247 @@
+use std::{ collections::HashMap, path::Path };
+
+use anyhow::{ Result, Context };
+use atom_syndication::*;
+use chrono::{ Utc, TimeZone };
+use url::Url;
+
+use super::{ Issue, Image, FeedMeta, HISTORY_NAMESPACE, xml_entity_escape, write_atomic, digest::Digest };
+
+// atom_syndication writes text elements as they are, so text is escaped here.
+// Attributes like category terms and link targets are escaped by the crate
+
+/// Entry of `issue`, with `summary` as plain text and `image` as enclosure. The content is left out
+/// if the body is empty in favor of the summary, as with --without-content.
+/// `categories` are the state and labels of the issue, labels with the web page of their issues as scheme.
+/// The body is plain text instead of HTML if `text`
+pub fn issue_to_entry(issue: &Issue, link: &str, id: &str, categories: &[(String, Option<Url>)], author_uri: Url,
+                      summary: Option<&str>, image: Option<&Image>, text: bool) -> Result<Entry> {
+    let categories = categories.iter()
+        .map(|(name, label_url)| Category {
+            term: name.clone(),
+            scheme: label_url.as_ref().map(|url| url.to_string()),
+            label: Some(name.clone())
+        })
+        .collect::<Vec<_>>();
+
+    let content = if issue.body.is_empty() && summary.is_some() { None } else {
+        Some(ContentBuilder::default()
+            .content_type(Some(String::from(if text { "text" } else { "html" })))
+            .value(xml_entity_escape(&issue.body))
+            .build()
+            .expect("Failed to build content"))
+    };
+
+    let mut links = vec![
+        LinkBuilder::default()
+            .href(link.to_owned())
+            .build()
+            .expect("Failed to build link")
+    ];
+    if let Some(image) = image {
+        links.push(LinkBuilder::default()
+            .href(image.url.clone())
+            .rel("enclosure")
+            .mime_type(Some(image.mime_type.to_owned()))
+            .build()
+            .map_err(anyhow::Error::msg)?);
+    }
+
+    Ok(EntryBuilder::default()
+        .title(xml_entity_escape(&issue.title))
+        .id(xml_entity_escape(id))
+        .updated(Utc.timestamp(issue.updated_at, 0))
+        .published(issue.created_at.map(|created_at| Utc.timestamp(created_at, 0).into()))
+        .authors(vec![
+            Person {
+                uri: Some(xml_entity_escape(author_uri.as_str())),
+                name: xml_entity_escape(&issue.user_login),
+                email: None
+            }
+        ])
+        .categories(categories)
+        .links(links)
+        .summary(summary.map(xml_entity_escape))
+        .content(content)
+        .build()
+        .map_err(anyhow::Error::msg)
+        .context("Failed to build atom entry")?)
+}
+
+/// Entry of `digest`, by the repository `author` with the web page `author_uri`
+pub fn digest_to_entry(digest: &Digest, author: &str, author_uri: &str) -> Result<Entry> {
+    Ok(EntryBuilder::default()
+        .title(xml_entity_escape(&digest.title))
+        .id(xml_entity_escape(&digest.id))
+        .updated(Utc.timestamp(digest.updated, 0))
+        .authors(vec![
+            Person {
+                uri: Some(xml_entity_escape(author_uri)),
+                name: xml_entity_escape(author),
+                email: None
+            }
+        ])
+        .links(vec![
+            LinkBuilder::default()
+                .href(digest.link.clone())
+                .build()
+                .expect("Failed to build link")
+        ])
+        .content(ContentBuilder::default()
+            .content_type(Some(String::from("html")))
+            .value(xml_entity_escape(&digest.content))
+            .build()
+            .expect("Failed to build content"))
+        .build()
+        .map_err(anyhow::Error::msg)
+        .context("Failed to build atom entry")?)
+}
+
+/// Serialize the feed, `file` being its name in the feed directory
+pub fn render(meta: &FeedMeta, file: &str, entries: Vec<Entry>) -> Result<Vec<u8>> {
+    let mut feed = FeedBuilder::default();
+    feed.title(xml_entity_escape(&meta.title));
+    feed.subtitle(meta.subtitle.as_deref().map(xml_entity_escape));
+    feed.icon(meta.icon.map(xml_entity_escape));
+    feed.logo(meta.logo.map(xml_entity_escape));
+    feed.rights(meta.rights.map(xml_entity_escape));
+    feed.authors(meta.author
+        .map(|name| Person { name: xml_entity_escape(name), email: None, uri: None })
+        .into_iter()
+        .collect::<Vec<_>>());
+    feed.id(xml_entity_escape(meta.url));
+    feed.updated(meta.updated());
+    let mut links = vec![
+        LinkBuilder::default()
+            .href(meta.url)
+            .rel("alternate")
+            .build()
+            .map_err(anyhow::Error::msg)?
+    ];
+    if let Some(public_url) = &meta.public_url {
+        links.push(LinkBuilder::default()
+            .href(public_url.join(file)?.into_string())
+            .rel("self")
+            .mime_type(Some(String::from("application/atom+xml")))
+            .build()
+            .map_err(anyhow::Error::msg)?);
+    }
+    if let Some(hub) = meta.hub {
+        links.push(LinkBuilder::default()
+            .href(hub)
+            .rel("hub")
+            .build()
+            .map_err(anyhow::Error::msg)?);
+    }
+    for (rel, href) in &meta.archive.links {
+        links.push(LinkBuilder::default()
+            .href(href.clone())
+            .rel(*rel)
+            .mime_type(Some(String::from("application/atom+xml")))
+            .build()
+            .map_err(anyhow::Error::msg)?);
+    }
+    feed.links(links);
+    feed.entries(entries);
+    if meta.archive.archived {
+        let archive = extension::Extension { name: String::from("fh:archive"), ..Default::default() };
+        let mut history = HashMap::new();
+        history.insert(String::from("archive"), vec![ archive ]);
+        let mut extensions = HashMap::new();
+        extensions.insert(String::from("fh"), history);
+        feed.extensions(extensions);
+        let mut namespaces = HashMap::new();
+        namespaces.insert(String::from("fh"), String::from(HISTORY_NAMESPACE));
+        feed.namespaces(namespaces);
+    }
+
+    let feed = feed.build().expect("Failed to build Atom feed");
+    let xml = feed.write_to(Vec::new())?;
+    Ok(match meta.language {
+        // atom_syndication has no xml:lang, the validated language tag needs no escaping
+        Some(language) => String::from_utf8(xml)?
+            .replacen("<feed ", &format!("<feed xml:lang=\"{}\" ", language), 1)
+            .into_bytes(),
+        None => xml
+    })
+}
+
+pub fn write(feed_directory: &Path, file: &str, meta: &FeedMeta, entries: Vec<Entry>) -> Result<()> {
+    let content = render(meta, file, entries)?;
+    write_atomic(&feed_directory.join(file), |out_file| {
+        out_file.extend_from_slice(&content);
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TITLE: &str = "Fix <T> & \"U\" in ]]> markers";
+    static BODY: &str = "<p>Use &lt;T&gt; & \"U\", not ]]></p>";
+
+    fn render_issue(text: bool) -> (String, Feed) {
+        let issue = Issue {
+            number: 1,
+            state: 0,
+            title: TITLE.to_owned(),
+            body: BODY.to_owned(),
+            body_zstd: None,
+            body_markdown: false,
+            user_login: String::from("a&b"),
+            html_url: String::from("https://example.com/issues/1"),
+            updated_at: 0,
+            created_at: None,
+            reactions: None,
+            closed_at: None
+        };
+        let categories = [ (String::from("c++ & <d>"), Some(Url::parse("https://example.com/issues?q=a&b").unwrap())) ];
+        let entry = issue_to_entry(&issue, &issue.html_url, &issue.html_url, &categories,
+                                   Url::parse("https://example.com/a&b").unwrap(), Some(TITLE), None, text).unwrap();
+        let meta = FeedMeta {
+            title: String::from("Issues of a & b"),
+            subtitle: Some(String::from("<all> \"open\" issues")),
+            url: "https://example.com/issues?q=a&b",
+            icon: None,
+            logo: None,
+            author: Some("a & b"),
+            language: None,
+            rights: None,
+            public_url: None,
+            hub: None,
+            updated: Some(0),
+            archive: Default::default()
+        };
+        let xml = render(&meta, "atom.xml", vec![ entry ]).unwrap();
+        let feed = Feed::read_from(&xml[..]).expect("Invalid Atom");
+        (String::from_utf8(xml).unwrap(), feed)
+    }
+
+    #[test]
+    fn escapes_once() {
+        let (xml, feed) = render_issue(false);
+        assert!(xml.contains("<title>Fix &lt;T&gt; &amp; &quot;U&quot; in ]]&gt; markers</title>"), "{}", xml);
+
+        assert_eq!(feed.title(), "Issues of a & b");
+        assert_eq!(feed.subtitle(), Some("<all> \"open\" issues"));
+        assert_eq!(feed.authors()[0].name(), "a & b");
+        let entry = &feed.entries()[0];
+        assert_eq!(entry.title(), TITLE);
+        assert_eq!(entry.summary(), Some(TITLE));
+        assert_eq!(entry.authors()[0].name(), "a&b");
+        assert_eq!(entry.categories()[0].term(), "c++ & <d>");
+        assert_eq!(entry.categories()[0].scheme(), Some("https://example.com/issues?q=a&b"));
+        let content = entry.content().unwrap();
+        assert_eq!(content.content_type(), Some("html"));
+        assert_eq!(content.value(), Some(BODY));
+    }
+
+    #[test]
+    fn keeps_text_as_written() {
+        let (_, feed) = render_issue(true);
+        let content = feed.entries()[0].content().unwrap();
+        assert_eq!(content.content_type(), Some("text"));
+        assert_eq!(content.value(), Some(BODY));
+    }
+}
@@ -0,0 +1,65 @@
+use std::path::Path;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use super::{ xml_entity_escape, hex_color, write_atomic };
+
+/// Colors of the issue states, as used by GitHub
+static OPEN: &str = "1a7f37";
+static CLOSED: &str = "8250df";
+
+/// Black or white, whichever reads better on `background`
+fn text_color(background: &str) -> &'static str {
+    let channel = |i: usize| background.get(i..i + 2)
+        .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        .map_or(0.0, f64::from);
+    // Perceived brightness by ITU-R BT.601
+    let brightness = 0.299 * channel(0) + 0.587 * channel(2) + 0.114 * channel(4);
+    if background.len() == 6 && brightness < 150.0 { "fff" } else { "000" }
+}
+
+fn badge(name: &str, color: Option<&str>) -> String {
+    let style = match hex_color(color) {
+        Some(color) => format!("background: #{}; color: #{}", color, text_color(color)),
+        None => String::from("border: 1px solid #888")
+    };
+    format!("<span style=\"{}; border-radius: 1em; padding: 0 0.5em; font-size: 0.85em\">{}</span>",
+            style, xml_entity_escape(name))
+}
+
+/// Header of the entry content showing the issue state and its labels in their colors,
+/// for readers that don't display categories
+pub fn header(state: &str, labels: &[(String, Option<String>)]) -> String {
+    let state_color = match state {
+        "open" => Some(OPEN),
+        "closed" => Some(CLOSED),
+        _ => None
+    };
+    let badges = std::iter::once(badge(state, state_color))
+        .chain(labels.iter().map(|(name, color)| badge(name, color.as_deref())))
+        .collect::<Vec<_>>();
+    format!("<p>{}</p>\n", badges.join(" "))
+}
+
+/// https://shields.io/endpoint
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Endpoint<'a> {
+    schema_version: u8,
+    label: &'a str,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<&'a str>
+}
+
+/// Write badge.json of a feed directory, a shields.io endpoint showing the `open` issues of the feed `name`
+pub fn write_endpoint(feed_directory: &Path, name: &str, open: i64, color: Option<&str>) -> Result<()> {
+    let endpoint = Endpoint {
+        schema_version: 1,
+        label: name,
+        message: format!("{} open", open),
+        color: hex_color(color)
+    };
+    write_atomic(&feed_directory.join("badge.json"), |out| Ok(serde_json::to_writer(out, &endpoint)?))
+}
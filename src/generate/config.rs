@@ -0,0 +1,90 @@
+use std::{ fs, path::Path };
+
+use anyhow::{ anyhow, Result, Context };
+use structopt::StructOpt;
+use toml::Value;
+
+use crate::GenerateOpts;
+
+/// Keys holding the positional arguments of `generate`
+static POSITIONAL: &[&str] = &[ "repo", "out", "labels" ];
+
+/// Command line arguments equivalent to one [[feed]] table
+fn arguments(feed: &toml::value::Table) -> Result<Vec<String>> {
+    let mut options = Vec::new();
+    for (key, value) in feed {
+        if POSITIONAL.contains(&&key[..]) { continue }
+        let option = format!("--{}", key.replace('_', "-"));
+        // Tables are mappings, passed as <key>=<value>
+        if let Value::Table(table) = value {
+            for (name, value) in table {
+                let value = value.as_str()
+                    .ok_or_else(|| anyhow!("Values of {} must be strings", key))?;
+                options.extend(vec![ option.clone(), format!("{}={}", name, value) ]);
+            }
+            continue
+        }
+        let values = match value {
+            Value::Array(values) => values.iter().collect(),
+            value => vec![ value ]
+        };
+        for value in values {
+            match value {
+                Value::Boolean(true) => options.push(option.clone()),
+                Value::Boolean(false) => (),
+                Value::String(value) => options.extend(vec![ option.clone(), value.clone() ]),
+                Value::Integer(value) => options.extend(vec![ option.clone(), value.to_string() ]),
+                Value::Datetime(value) => options.extend(vec![ option.clone(), value.to_string() ]),
+                _ => return Err(anyhow!("Unsupported value of {}", key))
+            }
+        }
+    }
+
+    let string = |key: &str| match feed.get(key) {
+        Some(Value::String(value)) => Ok(value.clone()),
+        Some(_) => Err(anyhow!("{} must be a string", key)),
+        None => Err(anyhow!("{} is missing", key))
+    };
+
+    let mut arguments = vec![ String::from("generate") ];
+    arguments.extend(options);
+    arguments.push(String::from("--"));
+    arguments.push(string("repo")?);
+    arguments.push(string("out")?);
+    match feed.get("labels") {
+        Some(Value::Array(labels)) => for label in labels {
+            arguments.push(label.as_str()
+                .ok_or_else(|| anyhow!("labels must be strings"))?
+                .to_owned());
+        },
+        Some(_) => return Err(anyhow!("labels must be a list")),
+        None => ()
+    }
+    Ok(arguments)
+}
+
+/// Read the outputs described by the [[feed]] tables of a config file.
+/// Every table holds the long options of `generate`, and repo, out and labels for its arguments
+pub fn load(path: &Path) -> Result<Vec<GenerateOpts>> {
+    let config: Value = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?
+        .parse()
+        .with_context(|| format!("Invalid config file {}", path.display()))?;
+
+    let feeds = match config.get("feed") {
+        Some(Value::Array(feeds)) => feeds,
+        _ => return Err(anyhow!("{} has no [[feed]] tables", path.display()))
+    };
+
+    feeds.iter()
+        .enumerate()
+        .map(|(i, feed)| {
+            let feed = feed.as_table()
+                .ok_or_else(|| anyhow!("feed {} is not a table", i + 1))?;
+            let arguments = arguments(feed)
+                .with_context(|| format!("Invalid feed {} in {}", i + 1, path.display()))?;
+            GenerateOpts::from_iter_safe(arguments)
+                .map_err(|e| anyhow!("Invalid feed {} in {}: {}", i + 1, path.display(), e.message))
+        })
+        .collect()
+}
@@ -0,0 +1,100 @@
+use std::collections::BTreeMap;
+
+use chrono::{ Datelike, Duration, NaiveDate, TimeZone, Utc };
+
+use super::{ Issue, xml_entity_escape };
+
+/// Entry summarising the activity on the issues of a feed within one day or week
+pub struct Digest {
+    pub id: String,
+    pub title: String,
+    /// Web page of the issues in the feed
+    pub link: String,
+    /// HTML lists of the opened, closed and updated issues
+    pub content: String,
+    /// Time of the last activity within the period
+    pub updated: i64
+}
+
+#[derive(Default)]
+struct Activity<'a> {
+    opened: Vec<(&'a Issue, &'a str)>,
+    closed: Vec<(&'a Issue, &'a str)>,
+    updated: Vec<(&'a Issue, &'a str)>,
+    last: i64
+}
+
+/// First day of the day or week (starting on Monday) containing `time`, in UTC
+fn period_start(time: i64, period: &str) -> NaiveDate {
+    let date = Utc.timestamp(time, 0).date().naive_utc();
+    match period {
+        "week" => date - Duration::days(date.weekday().num_days_from_monday().into()),
+        _ => date
+    }
+}
+
+fn list(heading: &str, issues: &[(&Issue, &str)]) -> String {
+    if issues.is_empty() {
+        return String::new()
+    }
+    let items = issues.iter()
+        .map(|(issue, link)| format!("<li><a href=\"{}\">#{}</a> {}</li>",
+                                     xml_entity_escape(link), issue.number, xml_entity_escape(&issue.title)))
+        .collect::<String>();
+    format!("<h3>{}</h3><ul>{}</ul>", heading, items)
+}
+
+/// Digests of the feed `name` with the web page `url`, one per `period` with activity on `issues`,
+/// newest first. Issues are listed as opened and closed in the periods of their creation and closing,
+/// and as updated in the period of their last update. Only the last update is stored, so issues
+/// updated again move from the digest of an earlier period to the current one
+pub fn digests(name: &str, url: &str, period: &str, issues: &[Issue], links: &[String]) -> Vec<Digest> {
+    let mut periods = BTreeMap::<NaiveDate, Activity>::new();
+    for (issue, link) in issues.iter().zip(links) {
+        let mut listed = Vec::new();
+        if let Some(created_at) = issue.created_at {
+            let activity = periods.entry(period_start(created_at, period)).or_default();
+            activity.opened.push((issue, link));
+            activity.last = activity.last.max(created_at);
+            listed.push(period_start(created_at, period));
+        }
+        if let Some(closed_at) = issue.closed_at {
+            let activity = periods.entry(period_start(closed_at, period)).or_default();
+            activity.closed.push((issue, link));
+            activity.last = activity.last.max(closed_at);
+            listed.push(period_start(closed_at, period));
+        }
+        let start = period_start(issue.updated_at, period);
+        let activity = periods.entry(start).or_default();
+        if !listed.contains(&start) {
+            activity.updated.push((issue, link));
+        }
+        activity.last = activity.last.max(issue.updated_at);
+    }
+
+    periods.into_iter()
+        .rev()
+        .map(|(start, activity)| {
+            let date = start.format("%Y-%m-%d");
+            let counts = [ ("opened", activity.opened.len()), ("closed", activity.closed.len()),
+                           ("updated", activity.updated.len()) ]
+                .iter()
+                .filter(|(_, count)| *count > 0)
+                .map(|(kind, count)| format!("{} {}", count, kind))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let title = match period {
+                "week" => format!("{}: {} in the week of {}", name, counts, date),
+                _ => format!("{}: {} on {}", name, counts, date)
+            };
+            Digest {
+                id: format!("{}#{}-{}", url, period, date),
+                title,
+                link: url.to_owned(),
+                content: list("Opened", &activity.opened) + &list("Closed", &activity.closed)
+                    + &list("Updated", &activity.updated),
+                updated: activity.last
+            }
+        })
+        .collect()
+}
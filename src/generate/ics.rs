@@ -0,0 +1,85 @@
+use std::{ fs, path::Path };
+
+use anyhow::Result;
+use chrono::{ DateTime, Utc, TimeZone };
+
+use super::write_atomic;
+
+#[derive(sqlx::FromRow)]
+pub struct Milestone {
+    pub title: String,
+    pub html_url: String,
+    pub due_on: i64
+}
+
+pub struct ClosedIssue {
+    pub number: i64,
+    pub title: String,
+    pub html_url: String,
+    pub closed_at: i64
+}
+
+/// Escape TEXT values, RFC 5545 3.3.11
+fn text_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+        .replace('\r', "")
+}
+
+/// Append a content line, folded into lines of at most 75 octets, RFC 5545 3.1
+fn push_line(ics: &mut String, line: &str) {
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > 75 {
+            ics.push_str("\r\n ");
+            length = 1;
+        }
+        ics.push(c);
+        length += c.len_utf8();
+    }
+    ics.push_str("\r\n");
+}
+
+/// Append an event starting at `start`, a date or date-time property value taken at `time`
+fn push_event(ics: &mut String, uid: &str, time: DateTime<Utc>, start: &str, summary: &str, url: &str) {
+    push_line(ics, "BEGIN:VEVENT");
+    push_line(ics, &format!("UID:{}", text_escape(uid)));
+    push_line(ics, &format!("DTSTAMP:{}", time.format("%Y%m%dT%H%M%SZ")));
+    push_line(ics, &format!("DTSTART;{}", start));
+    push_line(ics, &format!("SUMMARY:{}", text_escape(summary)));
+    push_line(ics, &format!("URL:{}", url));
+    push_line(ics, "END:VEVENT");
+}
+
+/// Write milestones.ics, a calendar of the due dates of `milestones` as all-day events
+/// and of the closing of `closed` issues. Events are stamped with their own date, so
+/// unchanged calendars stay byte-identical
+pub fn write(out_path: &Path, title: &str, milestones: &[Milestone], closed: &[ClosedIssue]) -> Result<()> {
+    let mut ics = String::new();
+    push_line(&mut ics, "BEGIN:VCALENDAR");
+    push_line(&mut ics, "VERSION:2.0");
+    push_line(&mut ics, "PRODID:-//github-label-feed//EN");
+    push_line(&mut ics, &format!("X-WR-CALNAME:{}", text_escape(title)));
+
+    for milestone in milestones {
+        let due_on = Utc.timestamp(milestone.due_on, 0);
+        push_event(&mut ics, &milestone.html_url, due_on,
+                   &format!("VALUE=DATE:{}", due_on.format("%Y%m%d")),
+                   &format!("{} due", milestone.title), &milestone.html_url);
+    }
+    for issue in closed {
+        let closed_at = Utc.timestamp(issue.closed_at, 0);
+        push_event(&mut ics, &format!("{}#closed", issue.html_url), closed_at,
+                   &format!("VALUE=DATE-TIME:{}", closed_at.format("%Y%m%dT%H%M%SZ")),
+                   &format!("Closed #{}: {}", issue.number, issue.title), &issue.html_url);
+    }
+    push_line(&mut ics, "END:VCALENDAR");
+
+    fs::create_dir_all(out_path)?;
+    write_atomic(&out_path.join("milestones.ics"), |out| {
+        out.extend_from_slice(ics.as_bytes());
+        Ok(())
+    })
+}
@@ -0,0 +1,138 @@
+use std::{ fs, io::Write, path::Path };
+
+use anyhow::Result;
+
+use chrono::{ Utc, TimeZone };
+
+use crate::query;
+use super::{ Issue, FeedSummary, badges, xml_entity_escape, href_escape, hex_color, write_atomic };
+
+#[derive(sqlx::FromRow)]
+pub struct Milestone {
+    pub number: i64,
+    pub title: String,
+    pub html_url: String,
+    pub due_on: Option<i64>,
+    pub closed: bool
+}
+
+#[derive(sqlx::FromRow)]
+pub struct MilestoneIssue {
+    pub number: i64,
+    pub title: String,
+    pub html_url: String,
+    pub state: i64
+}
+
+static STYLE: &str = "body { font-family: sans-serif; max-width: 50em; margin: auto; padding: 1em }
+.color { display: inline-block; width: 0.8em; height: 0.8em; border-radius: 50%; margin-right: 0.4em }
+td { padding: 0.2em 0.6em }";
+
+fn page(title: &str, body: &str) -> String {
+    format!("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+             <style>\n{style}\n</style>\n</head>\n<body>\n<h1>{title}</h1>\n{body}</body>\n</html>\n",
+            title = xml_entity_escape(title), style = STYLE, body = body)
+}
+
+fn color_swatch(color: Option<&str>) -> String {
+    match hex_color(color) {
+        Some(color) => format!("<span class=\"color\" style=\"background: #{}\"></span>", color),
+        None => String::new()
+    }
+}
+
+fn format_links(prefix: &str, files: &[&str]) -> String {
+    files.iter()
+        .map(|file| format!("<a href=\"{}{}\">{}</a>", prefix, file, file))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Write index.html of a feed directory, listing its feed files and issues, `links` holding the link of each issue
+pub fn write_feed(feed_directory: &Path, name: &str, url: &str, files: &[&str], issues: &[Issue],
+                  links: &[String]) -> Result<()> {
+    let mut body = format!("<p><a href=\"{}\">{}</a></p>\n<p>{}</p>\n<ul>\n",
+                           xml_entity_escape(url), xml_entity_escape(url), format_links("", files));
+    for (issue, link) in issues.iter().zip(links) {
+        body.push_str(&format!("<li><a href=\"{}\">#{}</a> {}</li>\n",
+                               xml_entity_escape(link), issue.number, xml_entity_escape(&issue.title)));
+    }
+    body.push_str("</ul>\n");
+
+    write_atomic(&feed_directory.join("index.html"), |out| Ok(out.write_all(page(name, &body).as_bytes())?))
+}
+
+/// Write index.html of the output root, listing every generated feed
+pub fn write_root(out_path: &Path, title: &str, files: &[&str], feeds: &[FeedSummary]) -> Result<()> {
+    let mut body = String::from("<table>\n<tr><th>Feed</th><th>Issues</th><th>Formats</th></tr>\n");
+    for feed in feeds {
+        let directory = href_escape(&feed.directory);
+        body.push_str(&format!("<tr><td>{}<a href=\"{}/index.html\">{}</a></td><td>{}</td><td>{}</td></tr>\n",
+                               color_swatch(feed.color.as_deref()), directory, xml_entity_escape(&feed.name),
+                               feed.issues, format_links(&format!("{}/", directory), files)));
+    }
+    body.push_str("</table>\n");
+
+    fs::create_dir_all(out_path)?;
+    write_atomic(&out_path.join("index.html"), |out| Ok(out.write_all(page(title, &body).as_bytes())?))
+}
+
+/// Page of a single issue for --issue-pages, its state, labels and metadata above the content `body`
+pub fn issue_page(issue: &Issue, state: &str, labels: &[(String, Option<String>)], body: &str) -> String {
+    let date = |time: i64| Utc.timestamp(time, 0).format("%Y-%m-%d").to_string();
+    let mut metadata = format!("Opened by {}", xml_entity_escape(&issue.user_login));
+    if let Some(created_at) = issue.created_at {
+        metadata.push_str(&format!(" on {}", date(created_at)));
+    }
+    metadata.push_str(&format!(", updated on {}", date(issue.updated_at)));
+    if !issue.html_url.is_empty() {
+        metadata.push_str(&format!(" · <a href=\"{}\">{}</a>", xml_entity_escape(&issue.html_url),
+                                   xml_entity_escape(&issue.html_url)));
+    }
+
+    page(&format!("#{} {}", issue.number, issue.title),
+         &format!("{}<p>{}</p>\n{}\n", badges::header(state, labels), metadata, body))
+}
+
+fn milestone_issue_list(heading: &str, issues: &[&MilestoneIssue]) -> String {
+    let mut list = format!("<h2>{} ({})</h2>\n<ul>\n", heading, issues.len());
+    for issue in issues {
+        let number = if issue.html_url.is_empty() { format!("#{}", issue.number) } else {
+            format!("<a href=\"{}\">#{}</a>", xml_entity_escape(&issue.html_url), issue.number)
+        };
+        list.push_str(&format!("<li>{} {}</li>\n", number, xml_entity_escape(&issue.title)));
+    }
+    list.push_str("</ul>\n");
+    list
+}
+
+/// Page of a milestone for --milestone, its due date, the share of closed issues and all of its `issues`,
+/// like the milestone view of GitHub
+pub fn milestone_page(milestone: &Milestone, issues: &[MilestoneIssue]) -> String {
+    let (closed, open): (Vec<_>, Vec<_>) = issues.iter()
+        .partition(|issue| issue.state == query::issues::IssueState::CLOSED.to_integer());
+    // Rounded down, so only milestones without open issues are complete
+    let percent = if issues.is_empty() { 0 } else { closed.len() * 100 / issues.len() };
+
+    let mut status = Vec::new();
+    if let Some(due_on) = milestone.due_on {
+        status.push(format!("Due on {}", Utc.timestamp(due_on, 0).format("%Y-%m-%d")));
+    }
+    if milestone.closed {
+        status.push(String::from("Closed"));
+    }
+    let mut body = String::new();
+    if !milestone.html_url.is_empty() {
+        body.push_str(&format!("<p><a href=\"{}\">{}</a></p>\n", xml_entity_escape(&milestone.html_url),
+                               xml_entity_escape(&milestone.html_url)));
+    }
+    if !status.is_empty() {
+        body.push_str(&format!("<p>{}</p>\n", status.join(" · ")));
+    }
+    body.push_str(&format!("<p><progress value=\"{}\" max=\"{}\">{}%</progress> {}% complete, {} open, {} closed</p>\n",
+                           closed.len(), issues.len().max(1), percent, percent, open.len(), closed.len()));
+    body.push_str(&milestone_issue_list("Open", &open));
+    body.push_str(&milestone_issue_list("Closed", &closed));
+
+    page(&milestone.title, &body)
+}
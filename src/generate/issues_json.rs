@@ -0,0 +1,45 @@
+use std::path::Path;
+
+use anyhow::Result;
+use chrono::{ Utc, TimeZone };
+use serde::Serialize;
+
+use super::{ Issue, write_atomic };
+
+/// An issue of issues.json, for frontends reading the feeds' data without parsing XML
+#[derive(Serialize)]
+pub struct Record {
+    number: i64,
+    title: String,
+    state: String,
+    labels: Vec<String>,
+    author: String,
+    url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    created_at: Option<String>,
+    updated_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    closed_at: Option<String>
+}
+
+impl Record {
+    pub fn new(issue: &Issue, state: &str, labels: &[(String, Option<String>)]) -> Self {
+        let time = |time: i64| Utc.timestamp(time, 0).to_rfc3339();
+        Record {
+            number: issue.number,
+            title: issue.title.clone(),
+            state: state.to_owned(),
+            labels: labels.iter().map(|(name, _)| name.clone()).collect(),
+            author: issue.user_login.clone(),
+            url: issue.html_url.clone(),
+            created_at: issue.created_at.map(time),
+            updated_at: time(issue.updated_at),
+            closed_at: issue.closed_at.map(time)
+        }
+    }
+}
+
+/// Write issues.json of a feed directory, the issues of the feed in feed order
+pub fn write(feed_directory: &Path, records: &[Record]) -> Result<()> {
+    write_atomic(&feed_directory.join("issues.json"), |out| Ok(serde_json::to_writer_pretty(out, records)?))
+}
@@ -0,0 +1,155 @@
+use std::path::Path;
+
+use anyhow::Result;
+use chrono::{ Utc, TimeZone };
+use serde::Serialize;
+use url::Url;
+
+use super::{ Issue, Image, FeedMeta, write_atomic, digest::Digest };
+
+// https://www.jsonfeed.org/version/1.1/
+static VERSION: &str = "https://jsonfeed.org/version/1.1";
+
+#[derive(Serialize)]
+struct Feed {
+    version: &'static str,
+    title: String,
+    home_page_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    feed_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    /// Large image, the logo
+    #[serde(skip_serializing_if = "Option::is_none")]
+    icon: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    favicon: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    authors: Vec<Author>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    hubs: Vec<Hub>,
+    /// Page of older items with --page-size
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_url: Option<String>,
+    items: Vec<Item>
+}
+
+#[derive(Serialize, Clone)]
+pub struct Item {
+    id: String,
+    url: String,
+    title: String,
+    /// Either content_html or content_text is required, the latter holds
+    /// the summary if the content is left out
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_html: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date_published: Option<String>,
+    date_modified: String,
+    authors: Vec<Author>,
+    tags: Vec<String>
+}
+
+#[derive(Serialize)]
+struct Hub {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    url: String
+}
+
+#[derive(Serialize, Clone)]
+struct Author {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>
+}
+
+/// Item of `issue`, its body plain text instead of HTML if `text`
+pub fn issue_to_item(issue: &Issue, link: &str, id: &str, labels: &[String], author_uri: Url, summary: Option<&str>,
+                     image: Option<&Image>, text: bool) -> Item {
+    let without_content = issue.body.is_empty() && summary.is_some();
+    Item {
+        id: id.to_owned(),
+        url: link.to_owned(),
+        title: issue.title.clone(),
+        content_html: if without_content || text { None } else { Some(issue.body.clone()) },
+        content_text: if without_content { summary.map(str::to_owned) }
+                      else if text { Some(issue.body.clone()) } else { None },
+        summary: summary.map(str::to_owned),
+        image: image.map(|image| image.url.clone()),
+        date_published: issue.created_at.map(|created_at| Utc.timestamp(created_at, 0).to_rfc3339()),
+        date_modified: Utc.timestamp(issue.updated_at, 0).to_rfc3339(),
+        authors: vec![ Author {
+            name: issue.user_login.clone(),
+            url: Some(author_uri.into_string())
+        } ],
+        tags: labels.to_vec()
+    }
+}
+
+/// Item of `digest`, by the repository `author` with the web page `author_uri`
+pub fn digest_to_item(digest: &Digest, author: &str, author_uri: &str) -> Item {
+    Item {
+        id: digest.id.clone(),
+        url: digest.link.clone(),
+        title: digest.title.clone(),
+        content_html: Some(digest.content.clone()),
+        content_text: None,
+        summary: None,
+        image: None,
+        date_published: None,
+        date_modified: Utc.timestamp(digest.updated, 0).to_rfc3339(),
+        authors: vec![ Author {
+            name: author.to_owned(),
+            url: Some(author_uri.to_owned())
+        } ],
+        tags: Vec::new()
+    }
+}
+
+/// Serialize the feed, `file` being its name in the feed directory
+pub fn render(meta: &FeedMeta, file: &str, items: Vec<Item>) -> Result<Vec<u8>> {
+    let feed = Feed {
+        version: VERSION,
+        title: meta.title.clone(),
+        home_page_url: meta.url.to_owned(),
+        feed_url: meta.public_url.as_ref()
+            .map(|public_url| public_url.join(file))
+            .transpose()?
+            .map(Url::into_string),
+        description: meta.subtitle.clone(),
+        icon: meta.logo.map(str::to_owned),
+        favicon: meta.icon.map(str::to_owned),
+        authors: meta.author
+            .map(|name| Author { name: name.to_owned(), url: None })
+            .into_iter()
+            .collect(),
+        language: meta.language.map(str::to_owned),
+        hubs: meta.hub
+            .map(|url| Hub { kind: "WebSub", url: url.to_owned() })
+            .into_iter()
+            .collect(),
+        next_url: meta.archive.links.iter()
+            .find(|(rel, _)| *rel == "prev-archive")
+            .map(|(_, href)| href.clone()),
+        items
+    };
+
+    Ok(serde_json::to_vec_pretty(&feed)?)
+}
+
+pub fn write(feed_directory: &Path, file: &str, meta: &FeedMeta, items: Vec<Item>) -> Result<()> {
+    let content = render(meta, file, items)?;
+    write_atomic(&feed_directory.join(file), |out_file| {
+        out_file.extend_from_slice(&content);
+        Ok(())
+    })
+}
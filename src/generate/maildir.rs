@@ -0,0 +1,117 @@
+use std::{ fs::{ self, File }, io::Write, path::Path, collections::HashSet };
+
+use anyhow::{ Result, Context };
+use chrono::{ Utc, TimeZone };
+use tracing::info;
+
+pub struct Message {
+    pub number: i64,
+    pub subject: String,
+    pub author: String,
+    pub html_url: String,
+    pub updated_at: i64,
+    pub labels: Vec<String>,
+    /// HTML content of the entry
+    pub body: String
+}
+
+/// `text` reduced to characters allowed in the atoms of message ids and maildir file names
+fn atom(text: &str) -> String {
+    text.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' { c } else { '-' })
+        .collect()
+}
+
+/// Header value, as RFC 2047 encoded-words of at most 75 characters if not plain ASCII
+fn header_encode(text: &str) -> String {
+    let text = text.replace(|c: char| c.is_control(), " ");
+    if text.chars().all(|c| c.is_ascii()) && !text.contains("=?") {
+        return text
+    }
+
+    // 45 octets take 60 characters in base64, plus 12 for the delimiters and charset
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for c in text.chars() {
+        if word.len() + c.len_utf8() > 45 {
+            words.push(format!("=?UTF-8?B?{}?=", base64::encode(&word)));
+            word.clear();
+        }
+        word.push(c);
+    }
+    words.push(format!("=?UTF-8?B?{}?=", base64::encode(&word)));
+    words.join("\n ")
+}
+
+/// The message file of `message`, named after the issue and its update so every update
+/// of an issue is delivered once
+fn unique_name(repo: &str, message: &Message) -> String {
+    format!("{}.{}-{}.github-label-feed", message.updated_at, atom(repo), message.number)
+}
+
+fn render(repo: &str, host: &str, message: &Message) -> String {
+    let domain = format!("{}.{}", atom(&repo.replace('/', ".")), host);
+    let author = if message.author.is_ascii() {
+        format!("\"{}\"", message.author.replace('\\', "\\\\").replace('"', "\\\""))
+    } else { header_encode(&message.author) };
+    let mut mail = String::new();
+    mail.push_str(&format!("From: {} <noreply@{}>\n", author, host));
+    mail.push_str(&format!("Date: {}\n", Utc.timestamp(message.updated_at, 0).to_rfc2822()));
+    mail.push_str(&format!("Subject: {}\n", header_encode(&message.subject)));
+    mail.push_str(&format!("Message-ID: <{}.{}@{}>\n", message.number, message.updated_at, domain));
+    // Updates of an issue refer to the same, never delivered, first message, threading them
+    mail.push_str(&format!("References: <{}@{}>\n", message.number, domain));
+    mail.push_str(&format!("List-Id: {} <{}>\n", header_encode(repo), domain));
+    if !message.html_url.is_empty() {
+        mail.push_str(&format!("Archived-At: <{}>\n", message.html_url));
+    }
+    if !message.labels.is_empty() {
+        mail.push_str(&format!("Keywords: {}\n", header_encode(&message.labels.join(", "))));
+    }
+    mail.push_str("MIME-Version: 1.0\n");
+    mail.push_str("Content-Type: text/html; charset=utf-8\n");
+    mail.push_str("Content-Transfer-Encoding: base64\n\n");
+
+    let body = base64::encode(&message.body);
+    for line in body.as_bytes().chunks(76) {
+        mail.push_str(std::str::from_utf8(line).expect("base64 is ASCII"));
+        mail.push('\n');
+    }
+    mail
+}
+
+/// Deliver `messages` of the repository `repo` on the forge `host` to the Maildir `maildir`,
+/// skipping those already in new/ or cur/ from earlier runs
+pub fn write(maildir: &Path, repo: &str, host: &str, messages: &[Message]) -> Result<()> {
+    for directory in &[ "tmp", "new", "cur" ] {
+        fs::create_dir_all(maildir.join(directory))
+            .with_context(|| format!("Failed to create Maildir {}", maildir.display()))?;
+    }
+
+    // Mail readers move messages to cur/ and append flags after a colon
+    let mut delivered = HashSet::new();
+    for directory in &[ "new", "cur" ] {
+        for entry in fs::read_dir(maildir.join(directory))? {
+            let name = entry?.file_name().to_string_lossy().into_owned();
+            delivered.insert(name.split(':').next().unwrap_or_default().to_owned());
+        }
+    }
+
+    let mut count = 0;
+    for message in messages {
+        let name = unique_name(repo, message);
+        if delivered.contains(&name) { continue }
+
+        let temp_path = maildir.join("tmp").join(&name);
+        File::create(&temp_path)
+            .and_then(|mut file| {
+                file.write_all(render(repo, host, message).as_bytes())?;
+                file.sync_all()
+            })
+            .and_then(|()| fs::rename(&temp_path, maildir.join("new").join(&name)))
+            .with_context(|| format!("Failed to deliver {} to {}", name, maildir.display()))?;
+        count += 1;
+    }
+    info!("delivered {} messages to {}", count, maildir.display());
+    Ok(())
+}
@@ -0,0 +1,1853 @@
+use std::{
+    fs::{ self, File }, io::{ self, Write }, path::{ Path, PathBuf },
+    collections::{ BTreeMap, HashMap, HashSet }, hash::{ Hash, Hasher }, borrow::Cow,
+    time::Instant
+};
+
+use sqlx::prelude::*;
+use anyhow::{ Result, Context };
+use futures::StreamExt;
+use url::Url;
+use regex::Regex;
+use chrono::{ DateTime, Utc, TimeZone };
+use pulldown_cmark::{ Parser, Options };
+use flate2::write::GzEncoder;
+use tracing::info;
+use unicode_normalization::{ UnicodeNormalization, char::is_combining_mark };
+
+mod atom;
+mod rss;
+mod json_feed;
+mod index;
+mod opml;
+mod badges;
+mod summary;
+mod template;
+mod archive;
+mod ics;
+mod sitemap;
+mod maildir;
+mod issues_json;
+mod report;
+mod digest;
+pub mod config;
+
+use crate::{
+    parse_repo,
+    Conn, GenerateOpts, db,
+    backend::Backend,
+    query, search::{ self, Qualifier }
+};
+
+#[allow(dead_code)]
+#[derive(sqlx::FromRow, Clone, Hash)]
+struct Issue {
+    number: i64,
+    state: i64,
+    title: String,
+    body: String,
+    body_zstd: Option<Vec<u8>>,
+    body_markdown: bool,
+    user_login: String,
+    html_url: String,
+    updated_at: i64,
+    created_at: Option<i64>,
+    reactions: Option<i64>,
+    closed_at: Option<i64>
+}
+
+impl Issue {
+    /// Decompress the body and render it to HTML if it's Markdown
+    fn decompress(mut self) -> Result<Self> {
+        self.body = db::decode_body(self.body, self.body_zstd.take())?;
+        if self.body_markdown {
+            self.body = render_markdown(&self.body);
+            self.body_markdown = false;
+        }
+        Ok(self)
+    }
+
+    fn sanitize(mut self, sanitizer: &mut ammonia::Builder, repository_url: &Url) -> Self {
+        sanitizer.url_relative(url_rewriter(repository_url, &self.html_url));
+        self.body = sanitizer.clean(&self.body).to_string();
+        self
+    }
+}
+
+/// Resolution of the relative URLs in bodies, which only work on the forge itself:
+/// anchors point into the issue page, everything else below the repository page
+struct UrlRewriter {
+    repository_url: Url,
+    issue_url: Option<Url>
+}
+
+impl ammonia::UrlRelativeEvaluate for UrlRewriter {
+    fn evaluate<'a>(&self, url: &'a str) -> Option<Cow<'a, str>> {
+        let base = match &self.issue_url {
+            Some(issue_url) if url.starts_with('#') => issue_url,
+            _ => &self.repository_url
+        };
+        base.join(url).ok().map(|url| Cow::Owned(url.into_string()))
+    }
+}
+
+fn url_rewriter(repository_url: &Url, issue_url: &str) -> ammonia::UrlRelative {
+    ammonia::UrlRelative::Custom(Box::new(UrlRewriter {
+        repository_url: repository_url.clone(),
+        issue_url: Url::parse(issue_url).ok()
+    }))
+}
+
+/// Sanitizer of issue bodies, removing scripts, frames, styles and event handler attributes
+/// that issue authors could otherwise publish through the feeds
+fn sanitizer() -> ammonia::Builder<'static> {
+    let mut sanitizer = ammonia::Builder::default();
+    // Checkboxes of Markdown task lists
+    sanitizer.add_tags(&[ "input" ])
+        .add_tag_attributes("input", &[ "type", "checked", "disabled" ]);
+    sanitizer
+}
+
+/// Image embedded in an issue, shown by feed readers as thumbnail of its entry
+pub struct Image {
+    pub url: String,
+    /// image/* where the file extension doesn't tell
+    pub mime_type: &'static str
+}
+
+/// The first image of the sanitized HTML `body` with an absolute http(s) URL
+fn first_image(body: &str) -> Option<Image> {
+    body.match_indices("<img ").find_map(|(start, _)| {
+        let tag = &body[start..start + body[start..].find('>')?];
+        let src = &tag[tag.find(" src=\"")? + 6..];
+        let url = Url::parse(&src[..src.find('"')?].replace("&amp;", "&")).ok()?;
+        if url.scheme() != "http" && url.scheme() != "https" {
+            return None
+        }
+
+        let extension = url.path().rsplit('.').next().unwrap_or_default().to_ascii_lowercase();
+        let mime_type = match &extension[..] {
+            "png" => "image/png",
+            "jpg" | "jpeg" => "image/jpeg",
+            "gif" => "image/gif",
+            "webp" => "image/webp",
+            "svg" => "image/svg+xml",
+            _ => "image/*"
+        };
+        Some(Image { url: url.into_string(), mime_type })
+    })
+}
+
+/// Render Markdown with the GitHub extensions most used in issues
+fn render_markdown(markdown: &str) -> String {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_TASKLISTS | Options::ENABLE_STRIKETHROUGH;
+    let mut html = String::with_capacity(markdown.len() * 3 / 2);
+    pulldown_cmark::html::push_html(&mut html, Parser::new_ext(markdown, options));
+    html
+}
+
+/// A generated feed, as listed by index.html and feeds.opml
+pub struct FeedSummary {
+    pub name: String,
+    /// Web page of the issues in the feed
+    pub url: String,
+    pub directory: String,
+    pub issues: usize,
+    pub color: Option<String>
+}
+
+/// Feed-level metadata shared by all formats
+#[derive(Hash, Clone)]
+pub struct FeedMeta<'a> {
+    pub title: String,
+    pub subtitle: Option<String>,
+    /// Web page of the issues in the feed
+    pub url: &'a str,
+    pub icon: Option<&'a str>,
+    pub logo: Option<&'a str>,
+    pub author: Option<&'a str>,
+    /// Language tag of the feed content, e.g. en-US
+    pub language: Option<&'a str>,
+    /// Copyright notice of the feed
+    pub rights: Option<&'a str>,
+    /// Public URL of the feed directory, with --base-url
+    pub public_url: Option<Url>,
+    /// WebSub hub of the feeds, with --hub-url
+    pub hub: Option<&'a str>,
+    /// Last update of the feed with --deterministic, the current time if None
+    pub updated: Option<i64>,
+    /// Position among the pages of a feed split by --page-size
+    pub archive: Archive
+}
+
+/// Namespace of the RFC 5005 fh:archive element marking archive pages
+pub static HISTORY_NAMESPACE: &str = "http://purl.org/syndication/history/1.0";
+
+/// RFC 5005 archive of a feed split into pages, the current feed holding the newest entries
+/// and every archive page a full page of older entries
+#[derive(Hash, Clone, Default)]
+pub struct Archive {
+    /// The page is an archive rather than the current feed
+    pub archived: bool,
+    /// Links to the other pages with their relation: current or prev-archive
+    pub links: Vec<(&'static str, String)>
+}
+
+impl FeedMeta<'_> {
+    pub fn updated(&self) -> DateTime<Utc> {
+        self.updated.map_or_else(Utc::now, |updated| Utc.timestamp(updated, 0))
+    }
+}
+
+/// The public URL of the output, ending in / so files can be joined onto it
+pub fn root_url(base_url: &Url) -> Url {
+    let mut base_url = base_url.clone();
+    if !base_url.path().ends_with('/') {
+        let path = format!("{}/", base_url.path());
+        base_url.set_path(&path);
+    }
+    base_url
+}
+
+/// Public URL of `file` in the feed directory `directory`, given the public URL of the output
+pub fn file_url(base_url: &Url, directory: &str, file: &str) -> Result<Url> {
+    Ok(root_url(base_url).join(&format!("{}/{}", href_escape(directory), file))?)
+}
+
+/// Name of the feed `file` restricted to issues in `state`, the state prefixed to its name
+/// so that feeds of different formats don't collide
+fn state_file(file: &str, state: &str) -> String {
+    format!("{}.{}", state, file)
+}
+
+/// Name of the `archive`th archive page of the feed `file`, counting from 1 for the oldest entries
+fn archive_file(file: &str, archive: usize) -> String {
+    state_file(file, &format!("archive-{}", archive))
+}
+
+/// Link target of the feed file `file`, absolute with --base-url
+fn feed_href(public_url: &Option<Url>, file: &str) -> Result<String> {
+    Ok(match public_url {
+        Some(public_url) => public_url.join(file)?.into_string(),
+        None => file.to_owned()
+    })
+}
+
+/// Link to the page of issue `number` of --issue-pages from a feed directory, absolute with --base-url
+fn issue_page_href(feeds_url: Option<&Url>, number: i64) -> Result<String> {
+    let file = format!("issues/{}.html", number);
+    Ok(match feeds_url {
+        Some(feeds_url) => root_url(feeds_url).join(&file)?.into_string(),
+        None => format!("../{}", file)
+    })
+}
+
+/// Write the issue pages `pages` to issues/<number>.html, deleting pages of issues no longer in any feed
+fn write_issue_pages(out_path: &Path, pages: &BTreeMap<i64, String>, compressions: &[Compression]) -> Result<()> {
+    let directory = out_path.join("issues");
+    fs::create_dir_all(&directory)?;
+    for (number, page) in pages {
+        let path = directory.join(format!("{}.html", number));
+        write_atomic(&path, |out| Ok(out.write_all(page.as_bytes())?))?;
+        write_compressed(&path, compressions)?;
+    }
+
+    for entry in fs::read_dir(&directory)? {
+        let path = entry?.path();
+        let name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+        let stale = name.split('.').next()
+            .and_then(|number| number.parse::<i64>().ok())
+            .map_or(false, |number| !pages.contains_key(&number));
+        if stale {
+            fs::remove_file(&path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Number of full archive pages of `page_size` entries that `entries` entries fill,
+/// leaving between 1 and `page_size` entries to the current feed
+fn archive_count(entries: usize, page_size: Option<usize>) -> usize {
+    page_size.map_or(0, |size| entries.saturating_sub(1) / size)
+}
+
+/// Write `entries` to `file` and its archive pages of `page_size` entries each, deleting pages
+/// left over from longer feeds. `write` writes one page to a file of the feed directory.
+/// Archives are numbered from the oldest entry, so they keep their entries as new ones arrive,
+/// and are dated by their newest entry of `updated`, the update times of `entries`
+fn write_pages<T, F>(feed_directory: &Path, file: &str, meta: &FeedMeta, mut entries: Vec<T>, updated: &[i64],
+                     page_size: Option<usize>, mut write: F) -> Result<()>
+where F: FnMut(&str, &FeedMeta, Vec<T>) -> Result<()> {
+    let archives = archive_count(entries.len(), page_size);
+    let size = page_size.unwrap_or_default();
+    let current = entries.len() - archives * size;
+    let mut archived = entries.split_off(current).into_iter();
+
+    let links = if archives == 0 { Vec::new() } else {
+        vec![ ("prev-archive", feed_href(&meta.public_url, &archive_file(file, archives))?) ]
+    };
+    write(file, &FeedMeta { archive: Archive { archived: false, links }, ..meta.clone() }, entries)?;
+
+    // Entries are ordered from the newest, so the newest archive comes first
+    for archive in (1..=archives).rev() {
+        let start = current + (archives - archive) * size;
+        // next-archive is optional and left out, as it would change the newest archive once a newer one appears
+        let mut links = vec![ ("current", feed_href(&meta.public_url, file)?) ];
+        if archive > 1 {
+            links.push(("prev-archive", feed_href(&meta.public_url, &archive_file(file, archive - 1))?));
+        }
+        let meta = FeedMeta {
+            updated: updated[start..start + size].iter().copied().max(),
+            archive: Archive { archived: true, links },
+            ..meta.clone()
+        };
+        write(&archive_file(file, archive), &meta, archived.by_ref().take(size).collect())?;
+    }
+
+    for archive in archives + 1.. {
+        let path = feed_directory.join(archive_file(file, archive));
+        if !path.exists() { break }
+        fs::remove_file(&path)?;
+        for compression in &[ Compression::Gzip, Compression::Brotli ] {
+            let _ = fs::remove_file(compression.path(&path));
+        }
+    }
+    Ok(())
+}
+
+/// The entries built from those `issues` in `state`, `entries` holding one per issue in order
+fn of_state<T: Clone>(entries: &[T], issues: &[Issue], state: i64) -> Vec<T> {
+    entries.iter()
+        .zip(issues)
+        .filter(|(_, issue)| issue.state == state)
+        .map(|(entry, _)| entry.clone())
+        .collect()
+}
+
+/// Write `path` through a temporary file in the same directory, renamed into place once complete,
+/// so readers never see a partially written file. Files whose content didn't change are left
+/// untouched, keeping their mtime for HTTP caches and rsync
+fn write_atomic<F>(path: &Path, write: F) -> Result<()>
+where F: FnOnce(&mut Vec<u8>) -> Result<()> {
+    let mut content = Vec::new();
+    write(&mut content).with_context(|| format!("Failed to generate {}", path.display()))?;
+    if fs::read(path).map_or(false, |existing| existing == content) {
+        return Ok(())
+    }
+
+    let file_name = path.file_name()
+        .context("Output path has no file name")?
+        .to_string_lossy();
+    let temp_path = path.with_file_name(format!(".{}.tmp", file_name));
+
+    let result = File::create(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(&content)?;
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&temp_path, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result.with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Pre-compressed copy of generated files, for static servers configured to serve them in their place
+#[derive(Clone, Copy)]
+enum Compression {
+    Gzip,
+    Brotli
+}
+
+impl Compression {
+    /// Path of the compressed copy of `path`
+    fn path(self, path: &Path) -> PathBuf {
+        let mut path = path.as_os_str().to_owned();
+        path.push(match self {
+            Compression::Gzip => ".gz",
+            Compression::Brotli => ".br"
+        });
+        PathBuf::from(path)
+    }
+
+    fn compress(self, content: &[u8], out: &mut Vec<u8>) -> Result<()> {
+        match self {
+            Compression::Gzip => {
+                let mut encoder = GzEncoder::new(out, flate2::Compression::best());
+                encoder.write_all(content)?;
+                encoder.finish()?;
+            },
+            Compression::Brotli => {
+                brotli::BrotliCompress(&mut &content[..], out, &Default::default())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Write the compressed copies of the already written file `path` next to it
+fn write_compressed(path: &Path, compressions: &[Compression]) -> Result<()> {
+    if compressions.is_empty() {
+        return Ok(())
+    }
+    let content = fs::read(path)?;
+    for compression in compressions {
+        write_atomic(&compression.path(path), |out| compression.compress(&content, out))?;
+    }
+    Ok(())
+}
+
+/// `file` and its compressed copies, as listed by --report
+fn with_compressed(file: &str, compressions: &[Compression]) -> Vec<String> {
+    std::iter::once(file.to_owned())
+        .chain(compressions.iter().map(|compression| compression.path(Path::new(file)).to_string_lossy().into_owned()))
+        .collect()
+}
+
+/// Fingerprints of the generated feeds by directory, kept in the output directory
+/// to skip feeds whose entries didn't change since the last run
+#[derive(Default)]
+struct Fingerprints {
+    path: PathBuf,
+    feeds: BTreeMap<String, String>
+}
+
+impl Fingerprints {
+    const FILE: &'static str = ".fingerprints.json";
+
+    fn load(out_path: &Path) -> Result<Self> {
+        let path = out_path.join(Self::FILE);
+        let feeds = match fs::read(&path) {
+            Ok(content) => serde_json::from_slice(&content)
+                .with_context(|| format!("Invalid fingerprints in {}, delete it to rewrite all feeds", path.display()))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e.into())
+        };
+        Ok(Fingerprints { path, feeds })
+    }
+
+    /// Whether `fingerprint` of `directory` differs from the last run
+    fn changed(&self, directory: &str, fingerprint: u64) -> bool {
+        self.feeds.get(directory) != Some(&format!("{:016x}", fingerprint))
+    }
+
+    fn insert(&mut self, directory: &str, fingerprint: u64) {
+        self.feeds.insert(directory.to_owned(), format!("{:016x}", fingerprint));
+    }
+
+    /// Forget the feed directories of earlier runs missing from `directories`, returning them
+    fn retain(&mut self, directories: &[&str]) -> Vec<String> {
+        let stale = self.feeds.keys()
+            .filter(|directory| !directories.contains(&directory.as_str()))
+            .cloned()
+            .collect::<Vec<_>>();
+        for directory in &stale {
+            self.feeds.remove(directory);
+        }
+        stale
+    }
+
+    fn save(&self) -> Result<()> {
+        write_atomic(&self.path, |out| Ok(serde_json::to_writer_pretty(out, &self.feeds)?))
+    }
+}
+
+/// 64-bit FNV-1a, so fingerprints stay comparable across builds and Rust releases.
+/// Integers are fed as little-endian bytes of their own width (`usize` as 8 bytes),
+/// strings as their bytes followed by 0xff as by `Hash for str`.
+struct Fnv(u64);
+
+impl Default for Fnv {
+    fn default() -> Self {
+        Fnv(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) { self.write(&[i]) }
+    fn write_u16(&mut self, i: u16) { self.write(&i.to_le_bytes()) }
+    fn write_u32(&mut self, i: u32) { self.write(&i.to_le_bytes()) }
+    fn write_u64(&mut self, i: u64) { self.write(&i.to_le_bytes()) }
+    fn write_u128(&mut self, i: u128) { self.write(&i.to_le_bytes()) }
+    fn write_usize(&mut self, i: usize) { self.write_u64(i as u64) }
+    fn write_i8(&mut self, i: i8) { self.write_u8(i as u8) }
+    fn write_i16(&mut self, i: i16) { self.write_u16(i as u16) }
+    fn write_i32(&mut self, i: i32) { self.write_u32(i as u32) }
+    fn write_i64(&mut self, i: i64) { self.write_u64(i as u64) }
+    fn write_i128(&mut self, i: i128) { self.write_u128(i as u128) }
+    fn write_isize(&mut self, i: isize) { self.write_u64(i as u64) }
+}
+
+// Naive implementation of https://www.w3.org/TR/REC-xml/#syntax
+fn xml_entity_escape(from: &str) -> String {
+    let mut escaped = String::with_capacity(from.len());
+
+    for c in from.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '\'' => escaped.push_str("&apos;"),
+            '"' => escaped.push_str("&quot;"),
+            any => escaped.push(any)
+        }
+    }
+
+    escaped
+}
+
+/// Label colors are only used if they are plain hex RGB, as they end up in style attributes
+fn hex_color(color: Option<&str>) -> Option<&str> {
+    color.filter(|color| !color.is_empty() && color.len() <= 8 && color.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Percent-encode a path segment for use in a relative link
+pub fn href_escape(segment: &str) -> String {
+    let mut escaped = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'+' => escaped.push(byte as char),
+            byte => escaped.push_str(&format!("%{:02X}", byte))
+        }
+    }
+    escaped
+}
+
+fn path_escape(from: &str) -> String {
+    from.replace('/', "_")
+        .replace(char::is_whitespace, "_")
+}
+
+/// Days of RSS skipDays
+pub static DAYS: &[&str] = &[ "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday" ];
+
+/// Periods of --digest
+pub static PERIODS: &[&str] = &[ "day", "week" ];
+
+pub static MODES: &[&str] = &[ "updated", "created" ];
+
+pub static ENTRY_IDS: &[&str] = &[ "url", "tag" ];
+
+/// Entry id of `issue`. `tag` makes a tag: URI (RFC 4151) of the forge `host`, the day the issue was created
+/// and `tag_name`, the repository as named before any rename, and its number, which stays the same
+/// when the issue URL changes. Issues without creation time keep their URL
+fn entry_id(issue: &Issue, strategy: &str, host: &str, tag_name: &str) -> String {
+    match issue.created_at {
+        Some(created_at) if strategy == "tag" => {
+            let repository = tag_name.split('/').map(href_escape).collect::<Vec<_>>();
+            format!("tag:{},{}:{}/{}", host, Utc.timestamp(created_at, 0).format("%Y-%m-%d"), repository.join("/"), issue.number)
+        },
+        _ => issue.html_url.clone()
+    }
+}
+
+pub static SLUGS: &[&str] = &[ "underscore", "percent", "ascii" ];
+
+/// Directory name of the feed `name`. `underscore` replaces slashes and whitespace, as earlier versions did,
+/// `percent` percent-encodes all but unreserved characters and `ascii` transliterates to lowercase words
+/// joined by dashes, falling back to `percent` for names without any ASCII letters or digits
+fn slug(name: &str, strategy: &str) -> String {
+    let slug = match strategy {
+        // Dots are encoded too, keeping names like .. and hidden directories out
+        "percent" => name.bytes()
+            .map(|byte| match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'~' => (byte as char).to_string(),
+                byte => format!("%{:02X}", byte)
+            })
+            .collect(),
+        "ascii" => {
+            let mut slug = String::new();
+            for c in name.nfd().filter(|&c| !is_combining_mark(c)) {
+                if c.is_ascii_alphanumeric() {
+                    slug.push(c.to_ascii_lowercase());
+                } else if !slug.is_empty() && !slug.ends_with('-') {
+                    slug.push('-');
+                }
+            }
+            let slug = slug.trim_end_matches('-');
+            if slug.is_empty() { return self::slug(name, "percent") }
+            slug.to_owned()
+        },
+        _ => path_escape(name)
+    };
+    // Would address the output directory itself or its parent
+    if slug.is_empty() || slug == "." || slug == ".." {
+        return format!("_{}", slug.replace('.', "_"))
+    }
+    slug
+}
+
+/// Device names Windows reserves in every directory, with any extension
+static RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL",
+    "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+    "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9"
+];
+
+/// `slug` made valid on Windows: characters NTFS forbids replaced by _, trailing dots and spaces
+/// replaced and reserved device names prefixed with _
+fn portable(slug: &str) -> String {
+    let mut portable = slug.chars()
+        .map(|c| if c.is_control() || "<>:\"/\\|?*".contains(c) { '_' } else { c })
+        .collect::<String>();
+    let kept = portable.trim_end_matches(|c| c == '.' || c == ' ').len();
+    portable.replace_range(kept.., &"_".repeat(portable.len() - kept));
+    let stem = portable.split('.').next().unwrap_or_default();
+    if RESERVED_NAMES.iter().any(|reserved| stem.eq_ignore_ascii_case(reserved)) {
+        portable.insert(0, '_');
+    }
+    portable
+}
+
+/// Directory of the feed `name` within the output directory, as given by --directory
+/// or following --slug and --portable-paths
+fn directory_name(name: &str, opts: &GenerateOpts) -> String {
+    if let Some((_, directory)) = opts.directory.iter().find(|(mapped, _)| mapped == name) {
+        return directory.clone()
+    }
+    let slug = slug(name, &opts.slug);
+    if opts.portable_paths { portable(&slug) } else { slug }
+}
+
+/// Fail if two of the feeds `names` would be written to the same directory
+fn check_directories<'a>(names: impl Iterator<Item = &'a str>, opts: &GenerateOpts) -> Result<()> {
+    let mut directories = HashMap::new();
+    for name in names {
+        let directory = directory_name(name, opts);
+        // Windows file systems ignore case
+        let key = if opts.portable_paths { directory.to_lowercase() } else { directory.clone() };
+        match directories.insert(key, name) {
+            Some(other) if other != name => return Err(anyhow::anyhow!(
+                "Feeds {:?} and {:?} would both be written to {}, choose another --slug or map one with --directory",
+                other, name, directory)),
+            _ => ()
+        }
+    }
+    Ok(())
+}
+
+pub static SORT_KEYS: &[&str] = &[ "number", "updated", "created", "reactions" ];
+pub static ORDERS: &[&str] = &[ "desc", "asc" ];
+pub static CONTENT_TYPES: &[&str] = &[ "html", "text" ];
+pub static LAYOUTS: &[&str] = &[ "flat", "nested" ];
+
+/// ORDER BY clause of `--sort` and `--order`, unknown values last and ties broken by number
+fn order_by(sort: &str, order: &str) -> String {
+    let column = match sort {
+        "updated" => "updated_at",
+        "created" => "created_at",
+        "reactions" => "reactions",
+        _ => "number"
+    };
+    let order = if order == "asc" { "ASC" } else { "DESC" };
+    format!("{} IS NULL, {} {}, number {}", column, column, order, order)
+}
+
+/// Translate a glob with `*` and `?` wildcards into an anchored regex
+fn glob_to_regex(glob: &str) -> Result<Regex, regex::Error> {
+    let mut regex = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            c => regex.push_str(&regex::escape(c.encode_utf8(&mut [0; 4])))
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex)
+}
+
+/// `glob` as LIKE pattern escaped by `\`. LIKE ignores ASCII case, as forges do for logins
+fn glob_to_like(glob: &str) -> String {
+    let mut like = String::new();
+    for c in glob.chars() {
+        match c {
+            '*' => like.push('%'),
+            '?' => like.push('_'),
+            '%' | '_' | '\\' => { like.push('\\'); like.push(c) },
+            c => like.push(c)
+        }
+    }
+    like
+}
+
+pub static GROUPINGS: &[&str] = &[ "label", "assignee", "author" ];
+
+/// Feed of the issues nobody is assigned to, with `--by assignee`
+static UNASSIGNED: &str = "unassigned";
+
+/// Feed of the issues without any label, with `--include-unlabeled`
+static UNLABELED: &str = "_unlabeled";
+
+/// Combines labels in a feed of the issues carrying all of them, as in bug+regression
+static LABEL_SEPARATOR: char = '+';
+
+/// Page of the progress of a milestone, written to its feed directory
+static MILESTONE_PAGE: &str = "milestone.html";
+
+/// Condition matching issues labeled `?` or one of its aliases
+static LABELED: &str =
+    "EXISTS (SELECT 1 FROM is_labeled JOIN labels ON is_labeled.label=labels.id
+             WHERE is_labeled.repo=issues.repo AND is_labeled.issue=issues.number
+               AND (labels.name=? OR labels.name IN (SELECT alias FROM label_aliases WHERE label=?)))";
+
+/// Condition matching issues with any label
+static LABELED_ANY: &str =
+    "EXISTS (SELECT 1 FROM is_labeled WHERE is_labeled.repo=issues.repo AND is_labeled.issue=issues.number)";
+
+/// Condition matching issues assigned to the user with login `?`
+static ASSIGNED: &str =
+    "EXISTS (SELECT 1 FROM is_assigned JOIN users ON is_assigned.user=users.id
+             WHERE is_assigned.repo=issues.repo AND is_assigned.issue=issues.number
+               AND users.login=?)";
+
+/// Condition matching issues assigned to anyone
+static ASSIGNED_ANYONE: &str =
+    "EXISTS (SELECT 1 FROM is_assigned WHERE is_assigned.repo=issues.repo AND is_assigned.issue=issues.number)";
+
+/// Condition on `issues` of one qualifier of a text query, and its parameters
+fn qualifier_condition(qualifier: &Qualifier) -> (String, Vec<&str>) {
+    match qualifier {
+        Qualifier::Term(term) => (
+            "EXISTS (SELECT 1 FROM issue_terms
+                     WHERE issue_terms.repo=issues.repo AND issue_terms.issue=issues.number
+                       AND issue_terms.term=?)".to_owned(),
+            vec![ term ]
+        ),
+        Qualifier::State(state) => (format!("state={}", state), vec![]),
+        Qualifier::Label(labels) => (vec![ LABELED; labels.len() ].join(" OR "),
+                                     labels.iter().flat_map(|label| vec![ label.as_str(), label ]).collect()),
+        Qualifier::Milestone(title) => (
+            "EXISTS (SELECT 1 FROM milestones
+                     WHERE milestones.repo=issues.repo AND milestones.number=issues.milestone
+                       AND milestones.title=?)".to_owned(),
+            vec![ title ]
+        ),
+        Qualifier::Author(login) => ("user_login=?".to_owned(), vec![ login ]),
+        Qualifier::Assignee(login) => (ASSIGNED.to_owned(), vec![ login ]),
+        Qualifier::NoLabel => (format!("NOT {}", LABELED_ANY), vec![]),
+        Qualifier::NoMilestone => ("milestone IS NULL".to_owned(), vec![]),
+        Qualifier::NoAssignee => (format!("NOT {}", ASSIGNED_ANYONE), vec![])
+    }
+}
+
+/// The issues making up one feed
+enum Selection {
+    Label(String),
+    /// Labels joined by LABEL_SEPARATOR
+    AllLabels(String),
+    Unlabeled,
+    Assignee(String),
+    Unassigned,
+    Author(String),
+    /// Text query with its alternatives, see search::parse_query
+    Query(String, Vec<Vec<(bool, Qualifier)>>),
+    Milestone(index::Milestone)
+}
+
+impl Selection {
+    fn name(&self) -> &str {
+        match self {
+            Selection::Label(name) | Selection::AllLabels(name)
+            | Selection::Assignee(name) | Selection::Author(name) | Selection::Query(name, _) => name,
+            Selection::Milestone(milestone) => &milestone.title,
+            Selection::Unassigned => UNASSIGNED,
+            Selection::Unlabeled => UNLABELED
+        }
+    }
+
+    /// Condition on `issues` selecting the issues of this feed, and its parameters
+    fn condition(&self) -> (String, Vec<&str>) {
+        match self {
+            Selection::Label(label) => (LABELED.to_owned(), vec![ label, label ]),
+            Selection::AllLabels(labels) => {
+                let labels = labels.split(LABEL_SEPARATOR).collect::<Vec<_>>();
+                (vec![ LABELED; labels.len() ].join(" AND "),
+                 labels.into_iter().flat_map(|label| vec![ label, label ]).collect())
+            },
+            Selection::Unlabeled => (format!("NOT {}", LABELED_ANY), vec![]),
+            Selection::Assignee(login) => (ASSIGNED.to_owned(), vec![ login ]),
+            Selection::Unassigned => (format!("NOT {}", ASSIGNED_ANYONE), vec![]),
+            Selection::Author(login) => ("user_login=?".to_owned(), vec![ login ]),
+            Selection::Query(_, alternatives) => {
+                let mut params = Vec::new();
+                let alternatives_condition = alternatives.iter()
+                    .map(|qualifiers| {
+                        let conditions = qualifiers.iter()
+                            .map(|(include, qualifier)| {
+                                let (condition, qualifier_params) = qualifier_condition(qualifier);
+                                params.extend(qualifier_params);
+                                format!("{}({})", if *include { "" } else { "NOT " }, condition)
+                            })
+                            .collect::<Vec<_>>();
+                        // Qualifiers like is:issue hold for every stored issue
+                        if conditions.is_empty() { String::from("1") } else { conditions.join(" AND ") }
+                    })
+                    .map(|condition| format!("({})", condition))
+                    .collect::<Vec<_>>()
+                    .join(" OR ");
+                (format!("({})", alternatives_condition), params)
+            },
+            Selection::Milestone(milestone) => (format!("milestone={}", milestone.number), vec![])
+        }
+    }
+
+    /// Web page showing the issues of this feed, as far as the forge has one
+    fn url(&self, backend: Backend, base_url: &Url, owner: &str, name: &str) -> Url {
+        match self {
+            Selection::Label(label) => backend.label_url(base_url, owner, name, label),
+            // Not every forge can filter by several labels, the first one is the closest match
+            Selection::AllLabels(labels) => {
+                let first = labels.split(LABEL_SEPARATOR).next().unwrap_or_default();
+                backend.label_url(base_url, owner, name, first)
+            },
+            Selection::Assignee(login) | Selection::Author(login) => backend.user_url(base_url, login),
+            Selection::Milestone(milestone) => Url::parse(&milestone.html_url)
+                .unwrap_or_else(|_| backend.issues_url(base_url, owner, name)),
+            Selection::Unassigned | Selection::Unlabeled | Selection::Query(..) => backend.issues_url(base_url, owner, name)
+        }
+    }
+}
+
+/// Restrictions on the issues of every feed
+struct Filter {
+    state_mask: i64,
+    /// Time issues are dated and limited by, `updated_at`, or `created_at` with --mode created
+    time: &'static str,
+    /// Range of `time`, end excluded
+    updated: (i64, i64),
+    /// Number of most recent issues by `time` to keep, all if negative
+    limit: i64,
+    /// Least reactions and comments of included issues, unknown counts taken as 0
+    min_reactions: i64,
+    min_comments: i64,
+    /// LIKE patterns of authors whose issues are left out
+    exclude_authors: Vec<String>,
+    order_by: String
+}
+
+async fn query_issues(conn: &mut Conn, repo_id: i64, selection: &Selection, filter: &Filter) -> Result<Vec<Issue>> {
+    let (condition, params) = selection.condition();
+    let exclude_authors = filter.exclude_authors.iter()
+        .map(|_| r"AND user_login NOT LIKE ? ESCAPE '\'")
+        .collect::<Vec<_>>()
+        .join(" ");
+    let sql = format!(r#"
+        SELECT * FROM (
+            SELECT number, state, title, body, body_zstd, body_markdown, user_login, html_url, {time} AS updated_at,
+                   created_at, reactions, closed_at FROM issues
+            WHERE repo=? AND {condition}
+              AND state & ? != 0
+              AND {time} >= ? AND {time} < ?
+              AND coalesce(reactions, 0) >= ? AND coalesce(comment_count, 0) >= ?
+              {exclude_authors}
+            ORDER BY {time} DESC
+            LIMIT ?
+        )
+        ORDER BY {order_by}
+    "#, time = filter.time, condition = condition, exclude_authors = exclude_authors, order_by = filter.order_by);
+
+    let mut query = sqlx::query_as::<_, Issue>(&sql).bind(repo_id);
+    for param in params {
+        query = query.bind(param);
+    }
+    query = query.bind(filter.state_mask)
+        .bind(filter.updated.0).bind(filter.updated.1)
+        .bind(filter.min_reactions).bind(filter.min_comments);
+    for pattern in &filter.exclude_authors {
+        query = query.bind(pattern);
+    }
+    Ok(query.bind(filter.limit)
+        .fetch_all(conn)
+        .await?)
+}
+
+/// Every label of the repository, aliases replaced by the label they stand for
+async fn all_labels(conn: &mut Conn, repo_id: i64) -> Result<Vec<Selection>> {
+    Ok(sqlx::query_as::<_, (String,)>(
+        "SELECT DISTINCT coalesce(label_aliases.label, labels.name) FROM labels
+         LEFT JOIN label_aliases ON label_aliases.alias=labels.name
+         WHERE repo=?"
+    ).bind(repo_id)
+     .fetch_all(conn)
+     .await?
+     .into_iter()
+     .map(|(label,)| Selection::Label(label))
+     .collect())
+}
+
+/// Every milestone of the repository
+async fn milestones(conn: &mut Conn, repo_id: i64) -> Result<Vec<index::Milestone>> {
+    Ok(sqlx::query_as::<_, index::Milestone>(
+        "SELECT number, title, html_url, due_on, closed FROM milestones WHERE repo=? ORDER BY number"
+    ).bind(repo_id)
+     .fetch_all(conn)
+     .await?)
+}
+
+/// Every issue of `milestone`, regardless of the filters of the feed
+async fn milestone_issues(conn: &mut Conn, repo_id: i64, milestone: &index::Milestone) -> Result<Vec<index::MilestoneIssue>> {
+    Ok(sqlx::query_as::<_, index::MilestoneIssue>(
+        "SELECT number, title, html_url, state FROM issues WHERE repo=? AND milestone=? ORDER BY number DESC"
+    ).bind(repo_id).bind(milestone.number)
+     .fetch_all(conn)
+     .await?)
+}
+
+/// Number of open issues of `selection`, regardless of the filters of the feed
+async fn count_open(conn: &mut Conn, repo_id: i64, selection: &Selection) -> Result<i64> {
+    let (condition, params) = selection.condition();
+    let sql = format!("SELECT count(*) FROM issues WHERE repo=? AND {} AND state=?", condition);
+    let mut query = sqlx::query_as::<_, (i64,)>(&sql).bind(repo_id);
+    for param in params {
+        query = query.bind(param);
+    }
+    Ok(query.bind(query::issues::IssueState::OPEN.to_integer())
+        .fetch_one(conn)
+        .await?.0)
+}
+
+async fn label_color(conn: &mut Conn, repo_id: i64, label: &str) -> Result<Option<String>> {
+    Ok(sqlx::query_as::<_, (Option<String>,)>(
+        "SELECT color FROM labels WHERE repo=? AND name=?"
+    ).bind(repo_id).bind(label)
+     .fetch_optional(conn)
+     .await?
+     .and_then(|(color,)| color))
+}
+
+#[derive(sqlx::FromRow)]
+struct Comment {
+    author: String,
+    created_at: i64,
+    body: String,
+    body_zstd: Option<Vec<u8>>,
+    body_markdown: bool
+}
+
+/// The last `limit` stored comments of issue `number` as HTML, oldest first
+async fn comments_html(conn: &mut Conn, repo_id: i64, number: i64, limit: u32,
+                       sanitizer: &ammonia::Builder<'_>) -> Result<String> {
+    let comments = sqlx::query_as::<_, Comment>(
+        "SELECT author, created_at, body, body_zstd, body_markdown FROM comments
+         WHERE repo=? AND issue=?
+         ORDER BY created_at DESC
+         LIMIT ?"
+    ).bind(repo_id).bind(number).bind(i64::from(limit))
+     .fetch_all(conn)
+     .await?;
+
+    let mut html = String::new();
+    for comment in comments.into_iter().rev() {
+        let mut body = db::decode_body(comment.body, comment.body_zstd)?;
+        if comment.body_markdown {
+            body = render_markdown(&body);
+        }
+        html.push_str(&format!("<hr>\n<p><b>{}</b> commented on {}:</p>\n{}\n",
+                               xml_entity_escape(&comment.author),
+                               Utc.timestamp(comment.created_at, 0).format("%Y-%m-%d %H:%M UTC"),
+                               sanitizer.clean(&body)));
+    }
+    Ok(html)
+}
+
+/// Names and colors of the labels of every issue of the repository, with aliases resolved,
+/// fetched at once instead of with a query per issue
+async fn labels_by_issue(conn: &mut Conn, repo_id: i64) -> Result<HashMap<i64, Vec<(String, Option<String>)>>> {
+    let rows = sqlx::query_as::<_, (i64, String, Option<String>)>(
+        "SELECT is_labeled.issue, coalesce(label_aliases.label, labels.name), max(labels.color) FROM is_labeled
+         JOIN labels ON is_labeled.label=labels.id
+         LEFT JOIN label_aliases ON label_aliases.alias=labels.name
+         WHERE is_labeled.repo=?
+         GROUP BY 1, 2
+         ORDER BY 1, 2"
+    ).bind(repo_id)
+     .fetch_all(conn)
+     .await?;
+
+    let mut labels = HashMap::<_, Vec<_>>::new();
+    for (issue, name, color) in rows {
+        labels.entry(issue).or_default().push((name, color));
+    }
+    Ok(labels)
+}
+
+/// Everyone assigned to an issue of the repository, followed by the unassigned issues
+async fn all_assignees(conn: &mut Conn, repo_id: i64) -> Result<Vec<Selection>> {
+    let mut assignees = sqlx::query_as::<_, (String,)>(
+        "SELECT DISTINCT users.login FROM is_assigned
+         JOIN users ON is_assigned.user=users.id
+         WHERE is_assigned.repo=?"
+    ).bind(repo_id)
+     .fetch_all(conn)
+     .await?
+     .into_iter()
+     .map(|(login,)| Selection::Assignee(login))
+     .collect::<Vec<_>>();
+    assignees.push(Selection::Unassigned);
+    Ok(assignees)
+}
+
+/// Everyone who opened an issue of the repository
+async fn all_authors(conn: &mut Conn, repo_id: i64) -> Result<Vec<Selection>> {
+    Ok(sqlx::query_as::<_, (String,)>(
+        "SELECT DISTINCT user_login FROM issues WHERE repo=?"
+    ).bind(repo_id)
+     .fetch_all(conn)
+     .await?
+     .into_iter()
+     .map(|(login,)| Selection::Author(login))
+     .collect())
+}
+
+/// Names of the repository feeds can be generated for: labels and their aliases,
+/// or logins with --by assignee|author
+async fn known_names(conn: &mut Conn, repo_id: i64, by: &str) -> Result<HashSet<String>> {
+    let selections = match by {
+        "assignee" => all_assignees(&mut *conn, repo_id).await?,
+        "author" => all_authors(&mut *conn, repo_id).await?,
+        _ => return Ok(sqlx::query_as::<_, (String,)>(
+            "SELECT name FROM labels WHERE repo=? UNION SELECT alias FROM label_aliases"
+        ).bind(repo_id)
+         .fetch_all(conn)
+         .await?
+         .into_iter()
+         .map(|(name,)| name)
+         .collect())
+    };
+    Ok(selections.iter().map(|selection| selection.name().to_owned()).collect())
+}
+
+/// State shared by the feeds of one run of generate, which are generated concurrently
+struct Generator<'a> {
+    opts: &'a GenerateOpts,
+    repo_id: i64,
+    backend: Backend,
+    base_url: &'a Url,
+    owner: &'a str,
+    name: &'a str,
+    /// owner/name in tag: entry ids
+    tag_name: &'a str,
+    out_path: &'a Path,
+    title: &'a str,
+    templates: &'a template::Templates<'a>,
+    filter: &'a Filter,
+    /// Feed files of the enabled formats
+    files: &'a [&'a str],
+    /// Files written to every feed directory
+    feed_files: &'a [&'a str],
+    compressions: &'a [Compression],
+    split_states: &'a [(String, i64)],
+    repository_url: &'a Url,
+    /// Avatar of the repository owner with --owner-avatar
+    avatar: Option<&'a str>,
+    /// Fingerprints of the last run
+    fingerprints: &'a Fingerprints,
+    /// Labels of every issue, see `labels_by_issue`
+    labels: &'a HashMap<i64, Vec<(String, Option<String>)>>
+}
+
+/// Outcome of generating one feed, merged into the output of the whole run
+struct GeneratedFeed {
+    summary: FeedSummary,
+    fingerprint: u64,
+    /// Whether the feed is written, or would be with --dry-run
+    outdated: bool,
+    /// Files written to the feed directory, relative to the output directory
+    files: Vec<String>,
+    /// Closed issues of the feed, for --ics-closed
+    closed: Vec<ics::ClosedIssue>,
+    /// Issues of the feed as mail, for --maildir
+    messages: Vec<maildir::Message>,
+    /// Pages of the issues of the feed by number, for --issue-pages
+    issue_pages: Vec<(i64, String)>
+}
+
+impl Generator<'_> {
+    /// Directory of the feed `feed_name` within the output directory
+    fn directory(&self, feed_name: &str) -> String {
+        directory_name(feed_name, self.opts)
+    }
+
+    /// Generate the feed of `selection`, unless it's unchanged since the last run.
+    /// None if the feed is empty and left out with --skip-empty
+    async fn feed(&self, conn: &mut Conn, selection: Selection) -> Result<Option<GeneratedFeed>> {
+        let mut sanitizer = sanitizer();
+        let feed_name = selection.name();
+        let feed_directory = self.out_path.join(self.directory(feed_name));
+        info!("generating {}", feed_directory.display());
+
+        let issues = query_issues(&mut *conn, self.repo_id, &selection, self.filter).await?
+            .into_iter()
+            .map(|issue| Ok(issue.decompress()?.sanitize(&mut sanitizer, self.repository_url)))
+            .collect::<Result<Vec<_>>>()?;
+        if self.opts.skip_empty && issues.is_empty() {
+            info!("skipping empty {}", feed_directory.display());
+            return Ok(None)
+        }
+
+        let closed = issues.iter()
+            .filter(|_| self.opts.ics_closed)
+            .filter_map(|issue| Some(ics::ClosedIssue {
+                number: issue.number,
+                title: issue.title.clone(),
+                html_url: issue.html_url.clone(),
+                closed_at: issue.closed_at?
+            }))
+            .collect();
+
+        let feed_url = selection.url(self.backend, self.base_url, self.owner, self.name).into_string();
+        let meta = FeedMeta {
+            title: self.templates.feed_title(feed_name, self.title)?,
+            subtitle: self.templates.feed_subtitle(feed_name, self.title)?,
+            url: &feed_url,
+            icon: self.opts.feed_icon.as_deref().or(self.avatar),
+            logo: self.opts.feed_logo.as_deref().or(self.avatar),
+            author: self.opts.feed_author.as_deref(),
+            language: self.opts.feed_language.as_deref(),
+            rights: self.opts.feed_rights.as_deref(),
+            public_url: self.opts.feeds_url.as_ref()
+                .map(|feeds_url| file_url(feeds_url, &self.directory(feed_name), ""))
+                .transpose()?,
+            hub: self.opts.hub_url.as_ref().map(Url::as_str),
+            // The newest entry, or the epoch for empty feeds
+            updated: if self.opts.deterministic {
+                Some(issues.iter().map(|issue| issue.updated_at).max().unwrap_or(0))
+            } else { None },
+            archive: Archive::default()
+        };
+
+        let mut atom_entries = Vec::new();
+        let mut rss_items = Vec::new();
+        let mut json_items = Vec::new();
+        let mut messages = Vec::new();
+        let mut issue_pages = Vec::new();
+        let mut links = Vec::new();
+        let mut records = Vec::new();
+
+        let mut fingerprint = Fnv::default();
+        meta.hash(&mut fingerprint);
+        self.feed_files.hash(&mut fingerprint);
+
+        for issue in &issues {
+            let state_label = query::issues::IssueState::from_integer(issue.state)
+                .expect("Inconsistent database, invalid issue state").to_string();
+            let labels_of_issue = self.labels.get(&issue.number).map_or(&[][..], Vec::as_slice);
+
+            let all_labels = state_label.iter()
+                .cloned()
+                .chain(labels_of_issue.iter().map(|(name, _)| name.clone()))
+                .collect::<Vec<_>>();
+
+            let summary = self.opts.summary_length
+                .map(|length| summary::summarize(&issue.body, length));
+            let image = first_image(&issue.body);
+            let (title, mut body) = self.templates.entry(issue, &all_labels, feed_name)?;
+            if self.opts.comments > 0 {
+                sanitizer.url_relative(url_rewriter(self.repository_url, &issue.html_url));
+                body.push_str(&comments_html(&mut *conn, self.repo_id, issue.number, self.opts.comments, &sanitizer).await?);
+            }
+            let state = state_label.as_deref().unwrap_or_default();
+            if self.opts.issues_json {
+                records.push(issues_json::Record::new(issue, state, labels_of_issue));
+            }
+            if self.opts.issue_pages {
+                let page = index::issue_page(&Issue { title: title.clone(), ..issue.clone() }, state, labels_of_issue,
+                                             &body);
+                issue_pages.push((issue.number, page));
+            }
+            if self.opts.badges {
+                body = format!("{}{}", badges::header(state, labels_of_issue), body);
+            }
+            if self.opts.maildir.is_some() {
+                messages.push(maildir::Message {
+                    number: issue.number,
+                    subject: if self.opts.title_template.is_some() { title.clone() } else {
+                        format!("[{}] #{}: {}", self.title, issue.number, title)
+                    },
+                    author: issue.user_login.clone(),
+                    html_url: issue.html_url.clone(),
+                    updated_at: issue.updated_at,
+                    labels: labels_of_issue.iter().map(|(name, _)| name.clone()).collect(),
+                    body: body.clone()
+                });
+            }
+            let text = self.opts.content_type == "text";
+            if text {
+                body = summary::plain_text(&body);
+            }
+            if self.opts.without_content {
+                body.clear();
+            }
+            let issue = &Issue { title, body, ..issue.clone() };
+            let link = if self.opts.link_local {
+                issue_page_href(self.opts.feeds_url.as_ref(), issue.number)?
+            } else { issue.html_url.clone() };
+            if self.opts.link_local {
+                link.hash(&mut fingerprint);
+            }
+            let id = entry_id(issue, &self.opts.entry_ids, self.base_url.host_str().unwrap_or("localhost"), self.tag_name);
+            if id != issue.html_url {
+                id.hash(&mut fingerprint);
+            }
+            issue.hash(&mut fingerprint);
+            summary.hash(&mut fingerprint);
+            image.as_ref().map(|image| &image.url).hash(&mut fingerprint);
+            all_labels.hash(&mut fingerprint);
+
+            links.push(link.clone());
+
+            if self.opts.atom {
+                let author_uri = self.backend.user_url(self.base_url, &issue.user_login);
+                let categories = state_label.iter()
+                    .map(|state| (state.clone(), None))
+                    .chain(labels_of_issue.iter().map(|(name, _)| {
+                        (name.clone(), Some(self.backend.label_url(self.base_url, self.owner, self.name, name)))
+                    }))
+                    .collect::<Vec<_>>();
+                atom_entries.push(atom::issue_to_entry(issue, &link, &id, &categories, author_uri, summary.as_deref(),
+                                                       image.as_ref(), text)?);
+            }
+
+            if self.opts.rss {
+                rss_items.push(rss::issue_to_item(issue, &link, &id, &all_labels[..], summary.as_deref(),
+                                             image.as_ref(), text)?);
+            }
+
+            if self.opts.json_feed {
+                let author_uri = self.backend.user_url(self.base_url, &issue.user_login);
+                json_items.push(json_feed::issue_to_item(issue, &link, &id, &all_labels[..], author_uri, summary.as_deref(),
+                                                         image.as_ref(), text));
+            }
+        }
+
+        self.opts.page_size.hash(&mut fingerprint);
+        if let Some(period) = &self.opts.digest {
+            period.hash(&mut fingerprint);
+            let digests = digest::digests(feed_name, &feed_url, period, &issues, &links);
+            let author_uri = self.repository_url.as_str();
+            if self.opts.atom {
+                atom_entries = digests.iter()
+                    .map(|digest| atom::digest_to_entry(digest, self.title, author_uri))
+                    .collect::<Result<_>>()?;
+            }
+            if self.opts.rss {
+                rss_items = digests.iter().map(rss::digest_to_item).collect::<Result<_>>()?;
+            }
+            if self.opts.json_feed {
+                json_items = digests.iter()
+                    .map(|digest| json_feed::digest_to_item(digest, self.title, author_uri))
+                    .collect();
+            }
+        }
+
+        let open = if self.opts.badge_json {
+            Some(count_open(&mut *conn, self.repo_id, &selection).await?)
+        } else { None };
+        let color = match &selection {
+            Selection::Label(label) => label_color(&mut *conn, self.repo_id, label).await?,
+            _ => None
+        };
+        if let Some(open) = open {
+            (open, &color).hash(&mut fingerprint);
+        }
+        let milestone_page = match &selection {
+            Selection::Milestone(milestone) if !self.opts.stdout => {
+                let issues = milestone_issues(&mut *conn, self.repo_id, milestone).await?;
+                Some(index::milestone_page(milestone, &issues))
+            },
+            _ => None
+        };
+        if let Some(page) = &milestone_page {
+            page.hash(&mut fingerprint);
+        }
+        let hints = rss::Hints {
+            ttl: self.opts.rss_ttl,
+            skip_hours: &self.opts.rss_skip_hours,
+            skip_days: &self.opts.rss_skip_days
+        };
+        if self.opts.rss && !hints.is_empty() {
+            hints.hash(&mut fingerprint);
+        }
+        let fingerprint = fingerprint.finish();
+        let changed = self.fingerprints.changed(&self.directory(feed_name), fingerprint);
+        let archives = archive_count(issues.len(), self.opts.page_size);
+        let page_files = (1..=archives)
+            .flat_map(|archive| self.files.iter().map(move |file| archive_file(file, archive)))
+            .collect::<Vec<_>>();
+        let written_files = self.feed_files.iter().copied()
+            .chain(page_files.iter().map(String::as_str))
+            .chain(milestone_page.as_ref().map(|_| MILESTONE_PAGE))
+            .collect::<Vec<_>>();
+        let missing = written_files.iter().any(|file| {
+            let path = feed_directory.join(file);
+            !path.exists() || self.compressions.iter().any(|compression| !compression.path(&path).exists())
+        });
+        let outdated = changed || missing || self.opts.force;
+        let mut files = Vec::new();
+        if self.opts.stdout {
+            let content = if self.opts.atom {
+                atom::render(&meta, &self.opts.atom_file, atom_entries)?
+            } else if self.opts.rss {
+                rss::render(&meta, &hints, &self.opts.rss_file, rss_items)?
+            } else {
+                json_feed::render(&meta, &self.opts.json_feed_file, json_items)?
+            };
+            io::stdout().write_all(&content)?;
+        } else if !outdated {
+            info!("{} is unchanged", feed_directory.display());
+        } else if self.opts.dry_run {
+            info!("{} would be written", feed_directory.display());
+        } else {
+            fs::create_dir_all(&feed_directory)?;
+
+            for (state, state_integer) in self.split_states {
+                let meta = FeedMeta { title: format!("{} ({})", meta.title, state), ..meta.clone() };
+                if self.opts.atom {
+                    atom::write(&feed_directory, &state_file(&self.opts.atom_file, state), &meta,
+                                of_state(&atom_entries, &issues, *state_integer))?;
+                }
+                if self.opts.rss {
+                    rss::write(&feed_directory, &state_file(&self.opts.rss_file, state), &meta, &hints,
+                               of_state(&rss_items, &issues, *state_integer))?;
+                }
+                if self.opts.json_feed {
+                    json_feed::write(&feed_directory, &state_file(&self.opts.json_feed_file, state), &meta,
+                                     of_state(&json_items, &issues, *state_integer))?;
+                }
+            }
+
+            let updated = issues.iter().map(|issue| issue.updated_at).collect::<Vec<_>>();
+            if self.opts.atom {
+                write_pages(&feed_directory, &self.opts.atom_file, &meta, atom_entries, &updated, self.opts.page_size,
+                            |file, meta, entries| atom::write(&feed_directory, file, meta, entries))?;
+            }
+
+            if self.opts.rss {
+                write_pages(&feed_directory, &self.opts.rss_file, &meta, rss_items, &updated, self.opts.page_size,
+                            |file, meta, items| rss::write(&feed_directory, file, meta, &hints, items))?;
+            }
+
+            if self.opts.json_feed {
+                write_pages(&feed_directory, &self.opts.json_feed_file, &meta, json_items, &updated, self.opts.page_size,
+                            |file, meta, items| json_feed::write(&feed_directory, file, meta, items))?;
+            }
+
+            if self.opts.issues_json {
+                issues_json::write(&feed_directory, &records)?;
+            }
+
+            if let Some(open) = open {
+                badges::write_endpoint(&feed_directory, feed_name, open, color.as_deref())?;
+            }
+
+            if self.opts.html_index {
+                index::write_feed(&feed_directory, feed_name, &feed_url, self.files, &issues, &links)?;
+            }
+
+            if let Some(page) = &milestone_page {
+                write_atomic(&feed_directory.join(MILESTONE_PAGE), |out| Ok(out.write_all(page.as_bytes())?))?;
+            }
+
+            for file in written_files {
+                write_compressed(&feed_directory.join(file), self.compressions)?;
+                files.extend(with_compressed(&format!("{}/{}", self.directory(feed_name), file), self.compressions));
+            }
+        }
+
+        Ok(Some(GeneratedFeed {
+            summary: FeedSummary {
+                name: feed_name.to_owned(),
+                url: feed_url,
+                directory: self.directory(feed_name),
+                issues: issues.len(),
+                color
+            },
+            fingerprint,
+            outdated,
+            files,
+            closed,
+            messages,
+            issue_pages
+        }))
+    }
+}
+
+pub async fn run(database: &db::Database, opts: GenerateOpts) -> Result<()> {
+    let started = Instant::now();
+    let report_path = opts.report.clone();
+    let repositories = match &opts.all {
+        Some(out_root) => run_all(database, &out_root.clone(), opts).await?,
+        None => vec![run_repository(database, opts).await?]
+    };
+    if let Some(path) = report_path {
+        report::write(&path, &report::Report { duration_secs: started.elapsed().as_secs_f64(), repositories })?;
+    }
+    Ok(())
+}
+
+/// Generate the feeds of every repository of the database to `<out_root>/<owner>/<name>`,
+/// each as if it were a `generate` command of its own
+async fn run_all(database: &db::Database, out_root: &Path, opts: GenerateOpts) -> Result<Vec<report::Repository>> {
+    let repos = query::list_repositories(&mut *database.acquire().await?).await?;
+    let mut reports = Vec::new();
+    for query::RepositoryInfo { owner, name, .. } in repos {
+        info!("generating {}/{}", owner, name);
+        let report = run_repository(database, GenerateOpts {
+            repo: Some(format!("{}/{}", owner, name)),
+            out_path: Some(out_root.to_owned()),
+            layout: String::from("nested"),
+            all: None,
+            ..opts.clone()
+        }).await.with_context(|| format!("Failed to generate {}/{}", owner, name))?;
+        reports.push(report);
+    }
+    Ok(reports)
+}
+
+/// `opts` writing to `<out_path>/<owner>/<name>`, served below `<base-url>/<owner>/<name>/`, for --layout nested
+fn nested(mut opts: GenerateOpts) -> Result<GenerateOpts> {
+    let (owner, name) = match &opts.repo {
+        Some(repo) => parse_repo(repo)?,
+        None => return Ok(opts)
+    };
+    // GitLab owners may be nested groups
+    let segments = owner.split('/').chain(std::iter::once(name.as_str())).collect::<Vec<_>>();
+    opts.out_path = opts.out_path.map(|out_path| segments.iter().fold(out_path, |path, segment| path.join(segment)));
+    if let Some(feeds_url) = &opts.feeds_url {
+        let path = segments.iter().map(|segment| format!("{}/", href_escape(segment))).collect::<String>();
+        opts.feeds_url = Some(root_url(feeds_url).join(&path)?);
+    }
+    Ok(opts)
+}
+
+async fn run_repository(database: &db::Database, opts: GenerateOpts) -> Result<report::Repository> {
+    let started = Instant::now();
+    let opts = if opts.layout == "nested" { nested(opts)? } else { opts };
+    if let Some(language) = &opts.feed_language {
+        if language.is_empty() || !language.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(anyhow::anyhow!("Invalid --feed-language {:?}, expected a language tag like en-US", language));
+        }
+    }
+    if let Some(hour) = opts.rss_skip_hours.iter().find(|hour| **hour > 23) {
+        return Err(anyhow::anyhow!("Invalid --rss-skip-hours {}, expected hours from 0 to 23", hour));
+    }
+    if opts.page_size == Some(0) {
+        return Err(anyhow::anyhow!("--page-size needs to be at least 1"));
+    }
+    if opts.opml && opts.feeds_url.is_none() {
+        return Err(anyhow::anyhow!("--opml requires --base-url, the public URL of the output"));
+    }
+    if opts.sitemap && opts.feeds_url.is_none() {
+        return Err(anyhow::anyhow!("--sitemap requires --base-url, the public URL of the output"));
+    }
+    if let Some(archive) = &opts.archive {
+        archive::is_gzip(archive)?;
+    }
+
+    let (repo, out_path) = match (&opts.repo, &opts.out_path) {
+        (Some(repo), Some(out_path)) => (repo, out_path.as_path()),
+        // --stdout writes nothing to the output directory
+        (Some(repo), None) if opts.stdout => (repo, Path::new(".")),
+        _ => return Err(anyhow::anyhow!("generate needs a repository and an output directory"))
+    };
+    let (ref owner, ref name) = parse_repo(repo)?;
+    let mut report = report::Repository {
+        repository: format!("{}/{}", owner, name),
+        out_path: out_path.display().to_string(),
+        duration_secs: 0.0,
+        files: Vec::new(),
+        feeds: Vec::new(),
+        skipped: Vec::new()
+    };
+    let mut conn = database.acquire().await?;
+    let repo_id = query::find_repo_id(&mut conn, owner, name).await?
+        .ok_or_else(|| anyhow::anyhow!("Unknown repository {}/{}", owner, name))?;
+    let (backend, base_url) = query::backend(&mut conn, repo_id).await?;
+
+    let by = opts.by.as_str();
+    let is_glob = |name: &str| name.contains(|c| c == '*' || c == '?');
+    // Names like `why?` or `c++` may be labels themselves, which select themselves
+    // rather than match as globs or combine labels
+    let known = if opts.labels.iter().chain(&opts.label).any(|name| is_glob(name) || name.contains(LABEL_SEPARATOR)) {
+        known_names(&mut conn, repo_id, by).await?
+    } else { HashSet::new() };
+    let (globs, names): (Vec<_>, Vec<_>) = opts.labels.iter()
+        .chain(&opts.label)
+        .cloned()
+        .partition(|name| is_glob(name) && !known.contains(name));
+    let mut patterns = globs.iter()
+        .map(|glob| glob_to_regex(glob))
+        .collect::<Result<Vec<_>, _>>()?;
+    patterns.extend(opts.regex.iter().cloned());
+
+    let mut selections = names.into_iter()
+        .map(|name| match by {
+            "assignee" if name == UNASSIGNED => Selection::Unassigned,
+            "assignee" => Selection::Assignee(name),
+            "author" => Selection::Author(name),
+            _ if name.contains(LABEL_SEPARATOR) && !known.contains(&name) => Selection::AllLabels(name),
+            _ => Selection::Label(name)
+        })
+        .collect::<Vec<_>>();
+
+    let other_feeds = !opts.query.is_empty() || !opts.milestone.is_empty() || opts.milestones;
+    // Feeds left out of a restricted selection weren't deleted, so their directories have to stay
+    if opts.prune_output && (!selections.is_empty() || !patterns.is_empty() || other_feeds) {
+        return Err(anyhow::anyhow!("--prune-output needs the feeds of all names, without names, globs, --regex, \
+                                    --query or milestones selected"));
+    }
+    if (selections.is_empty() && !other_feeds) || !patterns.is_empty() {
+        let all = match by {
+            "assignee" => all_assignees(&mut conn, repo_id).await?,
+            "author" => all_authors(&mut conn, repo_id).await?,
+            _ => all_labels(&mut conn, repo_id).await?
+        };
+        if patterns.is_empty() {
+            selections = all;
+        } else {
+            for selection in all {
+                let name = selection.name();
+                if patterns.iter().any(|pattern| pattern.is_match(name))
+                    && !selections.iter().any(|selected| selected.name() == name) {
+                    selections.push(selection);
+                }
+            }
+        }
+    }
+
+    if opts.include_unlabeled {
+        selections.push(Selection::Unlabeled);
+    }
+    for query in &opts.query {
+        selections.push(Selection::Query(query.clone(), search::parse_query(query)?));
+    }
+    if opts.milestones || !opts.milestone.is_empty() {
+        let milestones = milestones(&mut conn, repo_id).await?;
+        if let Some(unknown) = opts.milestone.iter().find(|title| !milestones.iter().any(|milestone| &&milestone.title == title)) {
+            return Err(anyhow::anyhow!("Unknown milestone {:?} of {}/{}, milestones are stored by GitHub sync",
+                                       unknown, owner, name));
+        }
+        selections.extend(milestones.into_iter()
+                          .filter(|milestone| opts.milestones || opts.milestone.contains(&milestone.title))
+                          .map(Selection::Milestone));
+    }
+    for (name, directory) in &opts.directory {
+        if directory.is_empty() || directory.starts_with('.') || directory.contains(|c| c == '/' || c == '\\') {
+            return Err(anyhow::anyhow!("Invalid directory {:?} of {}, needs to be a plain directory name", directory, name));
+        }
+    }
+    check_directories(selections.iter().map(Selection::name), &opts)?;
+    if opts.stdout && selections.len() != 1 {
+        return Err(anyhow::anyhow!("--stdout writes a single feed, but {} are selected", selections.len()));
+    }
+
+    let mut state_mask = !0;
+    if opts.without_open { state_mask &= !query::issues::IssueState::OPEN.to_integer(); }
+    if opts.without_closed { state_mask &= !query::issues::IssueState::CLOSED.to_integer(); }
+
+    let filter = Filter {
+        state_mask,
+        time: if opts.mode == "created" { "created_at" } else { "updated_at" },
+        updated: (opts.updated_since.map_or(i64::MIN, |time| time.timestamp()),
+                  opts.updated_before.map_or(i64::MAX, |time| time.timestamp())),
+        // SQLite treats a negative LIMIT as no limit
+        limit: if opts.max_entries == 0 { -1 } else { i64::from(opts.max_entries) },
+        min_reactions: i64::from(opts.min_reactions),
+        min_comments: i64::from(opts.min_comments),
+        exclude_authors: opts.exclude_author.iter().map(|glob| glob_to_like(glob)).collect(),
+        order_by: order_by(&opts.sort, &opts.order)
+    };
+
+    let entry_template = opts.entry_template.as_ref()
+        .map(|path| fs::read_to_string(path)
+             .with_context(|| format!("Failed to read {}", path.display())))
+        .transpose()?;
+    let templates = template::Templates::new(opts.title_template.as_deref(), entry_template.as_deref(),
+                                             opts.feed_template.as_deref(), opts.feed_subtitle.as_deref())?;
+    let title = format!("{}/{}", owner, name);
+
+    let files = [ (opts.atom, &opts.atom_file), (opts.rss, &opts.rss_file), (opts.json_feed, &opts.json_feed_file) ]
+        .iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|(_, file)| file.as_str())
+        .collect::<Vec<_>>();
+    let split = opts.split_states;
+    let split_states = [ query::issues::IssueState::OPEN, query::issues::IssueState::CLOSED ]
+        .iter()
+        .filter(|state| split && state_mask & state.to_integer() != 0)
+        .filter_map(|state| Some((state.to_string()?, state.to_integer())))
+        .collect::<Vec<_>>();
+    let state_files = split_states.iter()
+        .flat_map(|(state, _)| files.iter().map(move |file| state_file(file, state)))
+        .collect::<Vec<_>>();
+    let all_files = files.iter().copied()
+        .chain(state_files.iter().map(String::as_str))
+        .collect::<Vec<_>>();
+    // Archive pages are named like the state feeds, e.g. archive-1.atom.xml
+    let page_files = if opts.page_size.is_some() {
+        files.iter().map(|file| archive_file(file, 1)).collect()
+    } else { Vec::new() };
+    let reserved_files = all_files.iter().copied()
+        .chain(page_files.iter().map(String::as_str))
+        .collect::<Vec<_>>();
+    for (i, file) in reserved_files.iter().enumerate() {
+        if file.is_empty() || file.starts_with('.') || file.contains(|c| c == '/' || c == '\\') {
+            return Err(anyhow::anyhow!("Invalid feed file name {:?}, needs to be a plain file name", file));
+        }
+        if reserved_files[..i].contains(file) || ["index.html", "issues.json", "badge.json", "feeds.opml", "sitemap.xml"].contains(file) {
+            return Err(anyhow::anyhow!("Feed file name {} collides with another output file", file));
+        }
+    }
+    if opts.stdout && files.len() != 1 {
+        return Err(anyhow::anyhow!("--stdout needs exactly one of --atom, --rss and --json-feed"));
+    }
+    let compressions = [ (opts.gzip, Compression::Gzip), (opts.brotli, Compression::Brotli) ]
+        .iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|(_, compression)| *compression)
+        .collect::<Vec<_>>();
+    // Files written to every feed directory
+    let feed_files = all_files.iter()
+        .copied()
+        .chain(if opts.html_index { Some("index.html") } else { None })
+        .chain(if opts.issues_json { Some("issues.json") } else { None })
+        .chain(if opts.badge_json { Some("badge.json") } else { None })
+        .collect::<Vec<_>>();
+    let fingerprints = if opts.stdout { Fingerprints::default() } else { Fingerprints::load(out_path)? };
+    let repository_url = backend.repository_url(&base_url, owner, name);
+    let avatar = if opts.owner_avatar {
+        Some(backend.avatar_url(&base_url, owner)
+            .ok_or_else(|| anyhow::anyhow!("--owner-avatar is not supported for backend {}", backend))?)
+    } else { None };
+    let labels = labels_by_issue(&mut conn, repo_id).await?;
+    let tag_name = query::tag_name(&mut conn, repo_id).await?;
+    // The in-memory database has a single connection, which the feeds need
+    drop(conn);
+
+    let generator = Generator {
+        opts: &opts,
+        repo_id,
+        backend,
+        base_url: &base_url,
+        owner,
+        name,
+        tag_name: &tag_name,
+        out_path,
+        title: &title,
+        templates: &templates,
+        filter: &filter,
+        files: &files,
+        feed_files: &feed_files,
+        compressions: &compressions,
+        split_states: &split_states,
+        repository_url: &repository_url,
+        avatar: avatar.as_ref().map(Url::as_str),
+        fingerprints: &fingerprints,
+        labels: &labels
+    };
+    // Every feed queries on a connection of its own, so at most as many run at once as the pool holds
+    let jobs = opts.jobs.max(1).min(database.max_connections());
+    let names = selections.iter().map(|selection| selection.name().to_owned()).collect::<Vec<_>>();
+    let feeds = futures::stream::iter(selections)
+        .map(|selection| {
+            let generator = &generator;
+            async move {
+                let mut conn = database.acquire().await?;
+                generator.feed(&mut conn, selection).await
+            }
+        })
+        .buffered(jobs)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>>>()?;
+    report.skipped = names.into_iter()
+        .zip(&feeds)
+        .filter(|(_, feed)| feed.is_none())
+        .map(|(name, _)| name)
+        .collect();
+    let feeds = feeds.into_iter().flatten().collect::<Vec<_>>();
+    report.feeds = feeds.iter()
+        .map(|feed| report::Feed {
+            name: feed.summary.name.clone(),
+            directory: feed.summary.directory.clone(),
+            issues: feed.summary.issues,
+            changed: feed.outdated,
+            files: feed.files.clone()
+        })
+        .collect();
+    if opts.stdout {
+        return Ok(report)
+    }
+    if opts.dry_run {
+        for feed in &feeds {
+            println!("{}: {} issues, {}", out_path.join(&feed.summary.directory).display(), feed.summary.issues,
+                     if feed.outdated { "would be written" } else { "unchanged" });
+        }
+        report.duration_secs = started.elapsed().as_secs_f64();
+        return Ok(report)
+    }
+    // Feeds create their directories, but every feed may be left out by --skip-empty
+    fs::create_dir_all(out_path).with_context(|| format!("Failed to create {}", out_path.display()))?;
+
+    let mut fingerprints = fingerprints;
+    let mut summaries = Vec::new();
+    // Closed issues and messages of all feeds, once each
+    let mut closed = BTreeMap::new();
+    let mut messages = BTreeMap::new();
+    let mut issue_pages = BTreeMap::new();
+    for feed in feeds {
+        fingerprints.insert(&feed.summary.directory, feed.fingerprint);
+        for issue in feed.closed {
+            closed.entry(issue.number).or_insert(issue);
+        }
+        for message in feed.messages {
+            messages.entry(message.number).or_insert(message);
+        }
+        for (number, page) in feed.issue_pages {
+            issue_pages.entry(number).or_insert(page);
+        }
+        summaries.push(feed.summary);
+    }
+
+    if opts.prune_output {
+        let directories = summaries.iter().map(|feed| feed.directory.as_str()).collect::<Vec<_>>();
+        for directory in fingerprints.retain(&directories) {
+            // Only plain directory names, as generated feeds have them
+            if directory.is_empty() || directory.starts_with('.') || directory.contains(|c| c == '/' || c == '\\')
+                || (opts.issue_pages && directory == "issues") {
+                continue
+            }
+            let path = out_path.join(&directory);
+            if path.is_dir() {
+                info!("pruning {}", path.display());
+                fs::remove_dir_all(&path).with_context(|| format!("Failed to prune {}", path.display()))?;
+            }
+        }
+    }
+    if opts.html_index {
+        index::write_root(out_path, &title, &files, &summaries)?;
+        write_compressed(&out_path.join("index.html"), &compressions)?;
+        report.files.extend(with_compressed("index.html", &compressions));
+    }
+    if opts.opml {
+        if let Some(feeds_url) = &opts.feeds_url {
+            let json_file = if opts.json_feed { Some(opts.json_feed_file.as_str()) } else { None };
+            opml::write(out_path, &title, feeds_url, &files, json_file, &summaries)?;
+            write_compressed(&out_path.join("feeds.opml"), &compressions)?;
+            report.files.extend(with_compressed("feeds.opml", &compressions));
+        }
+    }
+    if opts.ics {
+        let milestones = sqlx::query_as::<_, ics::Milestone>(
+            "SELECT title, html_url, due_on FROM milestones
+             WHERE repo=? AND due_on IS NOT NULL
+             ORDER BY due_on, number"
+        ).bind(repo_id)
+         .fetch_all(&mut *database.acquire().await?)
+         .await?;
+        let mut closed = closed.into_iter().map(|(_, issue)| issue).collect::<Vec<_>>();
+        closed.sort_by_key(|issue| (issue.closed_at, issue.number));
+        ics::write(out_path, &title, &milestones, &closed)?;
+        write_compressed(&out_path.join("milestones.ics"), &compressions)?;
+        report.files.extend(with_compressed("milestones.ics", &compressions));
+    }
+    if opts.issue_pages {
+        write_issue_pages(out_path, &issue_pages, &compressions)?;
+        for number in issue_pages.keys() {
+            report.files.extend(with_compressed(&format!("issues/{}.html", number), &compressions));
+        }
+    }
+    if opts.sitemap {
+        if let Some(feeds_url) = &opts.feeds_url {
+            let root_files = [ (opts.html_index, "index.html"), (opts.opml, "feeds.opml"), (opts.ics, "milestones.ics") ]
+                .iter()
+                .filter(|(enabled, _)| *enabled)
+                .map(|(_, file)| *file)
+                .collect::<Vec<_>>();
+            let page_files = issue_pages.keys().map(|number| format!("issues/{}.html", number)).collect::<Vec<_>>();
+            let root_files = root_files.into_iter().chain(page_files.iter().map(String::as_str)).collect::<Vec<_>>();
+            sitemap::write(out_path, feeds_url, &root_files, &feed_files, &summaries)?;
+            write_compressed(&out_path.join("sitemap.xml"), &compressions)?;
+            report.files.extend(with_compressed("sitemap.xml", &compressions));
+        }
+    }
+    if let Some(maildir) = &opts.maildir {
+        let messages = messages.into_iter().map(|(_, message)| message).collect::<Vec<_>>();
+        maildir::write(maildir, &title, repository_url.host_str().unwrap_or("localhost"), &messages)?;
+    }
+    fingerprints.save()?;
+
+    if let Some(archive) = &opts.archive {
+        archive::write(out_path, archive)?;
+    }
+
+    report.duration_secs = started.elapsed().as_secs_f64();
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use structopt::StructOpt;
+
+    use super::*;
+
+    fn opts(args: &[&str]) -> GenerateOpts {
+        GenerateOpts::from_iter([ "generate", "foo/bar", "out" ].iter().chain(args))
+    }
+
+    #[test]
+    fn portable_reserved_names() {
+        assert_eq!(portable("con"), "_con");
+        assert_eq!(portable("CON.txt"), "_CON.txt");
+        assert_eq!(portable("console"), "console");
+    }
+
+    #[test]
+    fn portable_forbidden_characters() {
+        assert_eq!(portable("wontfix."), "wontfix_");
+        assert_eq!(portable("why?*"), "why__");
+        assert_eq!(portable("a/b"), "a_b");
+    }
+
+    #[test]
+    fn slug_special_directories() {
+        for strategy in SLUGS {
+            for name in &[ "", ".", ".." ] {
+                let slug = slug(name, strategy);
+                assert!(!slug.is_empty() && slug != "." && slug != "..", "{:?} of {:?} is {:?}", strategy, name, slug);
+                assert_eq!(portable(&slug), slug);
+            }
+        }
+        assert_eq!(slug("", "underscore"), "_");
+        assert_eq!(slug(".", "underscore"), "__");
+        assert_eq!(slug("..", "underscore"), "___");
+    }
+
+    #[test]
+    fn slug_separators() {
+        assert_eq!(slug("a/b", "underscore"), "a_b");
+        assert_eq!(slug("a/b", "percent"), "a%2Fb");
+        assert_eq!(slug("a/b", "ascii"), "a-b");
+        assert_eq!(slug("why?*", "ascii"), "why");
+    }
+
+    #[test]
+    fn portable_case_collisions() {
+        assert!(check_directories([ "Bug", "bug" ].iter().copied(), &opts(&[])).is_ok());
+        assert!(check_directories([ "Bug", "bug" ].iter().copied(), &opts(&[ "--portable-paths" ])).is_err());
+        assert!(check_directories([ "Bug", "bug" ].iter().copied(),
+                                  &opts(&[ "--portable-paths", "--directory", "Bug=bug-2" ])).is_ok());
+    }
+}
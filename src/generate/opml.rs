@@ -0,0 +1,27 @@
+use std::{ fs, io::Write, path::Path };
+
+use anyhow::Result;
+use url::Url;
+
+use super::{ FeedSummary, file_url, xml_entity_escape, write_atomic };
+
+/// Write feeds.opml, an outline of every generated feed file for import into feed readers.
+/// `json_file` is the name of JSON Feeds among `files`, all others are RSS or Atom
+pub fn write(out_path: &Path, title: &str, feeds_url: &Url, files: &[&str], json_file: Option<&str>,
+             feeds: &[FeedSummary]) -> Result<()> {
+    let mut opml = format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<opml version=\"2.0\">\n\
+                            <head>\n<title>{}</title>\n</head>\n<body>\n", xml_entity_escape(title));
+    for feed in feeds {
+        for file in files {
+            let kind = if Some(*file) == json_file { "json" } else { "rss" };
+            opml.push_str(&format!("<outline type=\"{}\" text=\"{}\" title=\"{}\" xmlUrl=\"{}\" htmlUrl=\"{}\"/>\n",
+                                   kind, xml_entity_escape(&feed.name), xml_entity_escape(&feed.name),
+                                   xml_entity_escape(file_url(feeds_url, &feed.directory, file)?.as_str()),
+                                   xml_entity_escape(&feed.url)));
+        }
+    }
+    opml.push_str("</body>\n</opml>\n");
+
+    fs::create_dir_all(out_path)?;
+    write_atomic(&out_path.join("feeds.opml"), |out| Ok(out.write_all(opml.as_bytes())?))
+}
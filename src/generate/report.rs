@@ -0,0 +1,48 @@
+use std::{ io::{ self, Write }, path::Path };
+
+use anyhow::Result;
+use serde::Serialize;
+
+use super::write_atomic;
+
+/// Outcome of a `generate` run, for wrapper scripts and CI
+#[derive(Serialize)]
+pub struct Report {
+    pub duration_secs: f64,
+    pub repositories: Vec<Repository>
+}
+
+#[derive(Serialize)]
+pub struct Repository {
+    pub repository: String,
+    pub out_path: String,
+    pub duration_secs: f64,
+    /// Files written to the output directory, relative to it
+    pub files: Vec<String>,
+    pub feeds: Vec<Feed>,
+    /// Feeds left out by --skip-empty
+    pub skipped: Vec<String>
+}
+
+#[derive(Serialize)]
+pub struct Feed {
+    pub name: String,
+    pub directory: String,
+    pub issues: usize,
+    /// Whether the feed changed since the last run, or is rewritten by --force
+    pub changed: bool,
+    /// Files written to the feed directory, relative to the output directory. Empty with --dry-run
+    pub files: Vec<String>
+}
+
+/// Write `report` to `path`, or to stdout if it's `-`
+pub fn write(path: &Path, report: &Report) -> Result<()> {
+    if path == Path::new("-") {
+        let mut stdout = io::stdout();
+        serde_json::to_writer_pretty(&mut stdout, report)?;
+        writeln!(stdout)?;
+        Ok(())
+    } else {
+        write_atomic(path, |out| Ok(serde_json::to_writer_pretty(out, report)?))
+    }
+}
@@ -0,0 +1,262 @@
+use std::path::Path;
+
+use anyhow::{ Result, Context };
+use chrono::{ Utc, TimeZone };
+use rss::*;
+
+use super::{ Issue, Image, FeedMeta, HISTORY_NAMESPACE, xml_entity_escape, write_atomic, digest::Digest };
+
+static NAMESPACES: &[(&str, &str)] = &[
+    ("atom", "http://www.w3.org/2005/Atom"),
+    ("content", "http://purl.org/rss/1.0/modules/content/"),
+    ("dc", "http://purl.org/dc/elements/1.1/")
+];
+
+/// Polling hints of RSS channels: minutes readers may cache the channel, and the hours (GMT) and days
+/// they should skip
+#[derive(Hash, Default)]
+pub struct Hints<'a> {
+    pub ttl: Option<u32>,
+    pub skip_hours: &'a [u8],
+    pub skip_days: &'a [String]
+}
+
+impl Hints<'_> {
+    pub fn is_empty(&self) -> bool {
+        self.ttl.is_none() && self.skip_hours.is_empty() && self.skip_days.is_empty()
+    }
+}
+
+/// rss escapes most text elements itself, but writes description and content:encoded
+/// unescaped into CDATA sections, which only need their end marker split up.
+/// Categories and guids are written as they are and need xml_entity_escape
+fn cdata_escape(html: &str) -> String {
+    html.replace("]]>", "]]]]><![CDATA[>")
+}
+
+/// Add isPermaLink="true" to the guids of `xml`. Text is escaped outside of CDATA sections, so any
+/// <guid> there is an element, while CDATA sections may quote it and are kept as they are
+fn explicit_permalinks(xml: &str) -> String {
+    let mut rest = xml;
+    let mut result = String::with_capacity(xml.len());
+    while let Some(start) = rest.find("<![CDATA[") {
+        // cdata_escape leaves no ]]> within a section
+        let end = rest[start..].find("]]>").map_or(rest.len(), |end| start + end + 3);
+        result.push_str(&rest[..start].replace("<guid>", "<guid isPermaLink=\"true\">"));
+        result.push_str(&rest[start..end]);
+        rest = &rest[end..];
+    }
+    result.push_str(&rest.replace("<guid>", "<guid isPermaLink=\"true\">"));
+    result
+}
+
+/// Item of `issue`, with `summary` as plain text description and `image` as enclosure.
+/// The content is left out if the body is empty in favor of the summary. content:encoded is always HTML,
+/// so a plain text body (`text`) is escaped to show as written. `id` is a permalink if it's the issue URL
+pub fn issue_to_item(issue: &Issue, link: &str, id: &str, labels: &[String], summary: Option<&str>,
+                     image: Option<&Image>, text: bool) -> Result<Item> {
+    let content = if text { xml_entity_escape(&issue.body) } else { issue.body.clone() };
+    let categories = labels.iter()
+        .map(|name| CategoryBuilder::default()
+             .name(xml_entity_escape(name))
+             .build())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err_str| anyhow::anyhow!(err_str))?;
+    // The issue URL or tag: URI identifies the item across regenerations
+    let guid = if id.is_empty() { None } else {
+        Some(GuidBuilder::default()
+             .value(xml_entity_escape(id))
+             .permalink(id == issue.html_url)
+             .build()
+             .map_err(anyhow::Error::msg)?)
+    };
+    // The size of the image is unknown, readers accept 0
+    let enclosure = image
+        .map(|image| EnclosureBuilder::default()
+             .url(image.url.clone())
+             .length("0")
+             .mime_type(image.mime_type)
+             .build())
+        .transpose()
+        .map_err(anyhow::Error::msg)?;
+
+    Ok(ItemBuilder::default()
+       .title(issue.title.clone())
+       .link(link.to_owned())
+       .guid(guid)
+       // RSS has no update time, readers take pubDate as the time the item appeared
+       .pub_date(Utc.timestamp(issue.created_at.unwrap_or(issue.updated_at), 0).to_rfc2822())
+       .categories(categories)
+       .enclosure(enclosure)
+       .description(summary.map(xml_entity_escape))
+       .content(if issue.body.is_empty() && summary.is_some() { None } else { Some(cdata_escape(&content)) })
+       .build()
+       .map_err(anyhow::Error::msg)
+       .context("Failed to build RSS item")?)
+}
+
+/// Item of `digest`. Its id is no permalink, the link leads to the issues of the feed
+pub fn digest_to_item(digest: &Digest) -> Result<Item> {
+    Ok(ItemBuilder::default()
+       .title(digest.title.clone())
+       .link(digest.link.clone())
+       .guid(GuidBuilder::default()
+             .value(xml_entity_escape(&digest.id))
+             .permalink(false)
+             .build()
+             .map_err(anyhow::Error::msg)?)
+       .pub_date(Utc.timestamp(digest.updated, 0).to_rfc2822())
+       .content(cdata_escape(&digest.content))
+       .build()
+       .map_err(anyhow::Error::msg)
+       .context("Failed to build RSS item")?)
+}
+
+/// Serialize the feed, `file` being its name in the feed directory
+pub fn render(meta: &FeedMeta, hints: &Hints, file: &str, items: Vec<Item>) -> Result<Vec<u8>> {
+    let mut channel = ChannelBuilder::default();
+    channel.title(meta.title.clone());
+    channel.link(meta.url);
+    // The description is HTML, the subtitle plain text
+    channel.description(meta.subtitle.as_deref().map(xml_entity_escape).unwrap_or_default());
+    channel.pub_date(meta.updated().to_rfc2822());
+    channel.language(meta.language.map(str::to_owned));
+    channel.copyright(meta.rights.map(str::to_owned));
+    channel.ttl(hints.ttl.map(|ttl| ttl.to_string()));
+    channel.skip_hours(hints.skip_hours.iter().map(u8::to_string).collect::<Vec<_>>());
+    channel.skip_days(hints.skip_days.to_vec());
+    channel.items(items);
+
+    // RSS has a single image, the logo fits it better than an icon
+    if let Some(url) = meta.logo.or(meta.icon) {
+        channel.image(ImageBuilder::default()
+            .url(url)
+            .title(meta.title.clone())
+            .link(meta.url)
+            .build()
+            .map_err(anyhow::Error::msg)?);
+    }
+    // managingEditor needs an email address, so the author goes to dc:creator
+    if let Some(author) = meta.author {
+        channel.dublin_core_ext(extension::dublincore::DublinCoreExtensionBuilder::default()
+            .creators(vec![ author.to_owned() ])
+            .build()
+            .map_err(anyhow::Error::msg)?);
+    }
+
+    let channel = channel.build().expect("Failed to build RSS channel");
+    let mut xml = String::from_utf8(channel.write_to(Vec::new())?)?;
+
+    // rss keeps namespaces and extension attributes in HashMaps, writing them in random order.
+    // They are spliced in by hand instead, so unchanged feeds stay byte-identical
+    let mut namespaces = NAMESPACES.iter()
+        .map(|(prefix, url)| format!(" xmlns:{}=\"{}\"", prefix, url))
+        .collect::<String>();
+    if meta.archive.archived {
+        namespaces.push_str(&format!(" xmlns:fh=\"{}\"", HISTORY_NAMESPACE));
+    }
+    xml = xml.replacen("<rss version=\"2.0\"", &format!("<rss version=\"2.0\"{}", namespaces), 1);
+
+    // rss leaves out isPermaLink="true" as the default, some readers only trust it when explicit
+    xml = explicit_permalinks(&xml);
+
+    // The location of the channel itself, of its archive pages and its hub are only expressible with atom:link
+    let mut links = meta.public_url.as_ref()
+        .map(|public_url| Ok::<_, anyhow::Error>(("self", public_url.join(file)?.into_string())))
+        .transpose()?
+        .into_iter()
+        .chain(meta.archive.links.iter().cloned())
+        .map(|(rel, href)| format!("<atom:link href=\"{}\" rel=\"{}\" type=\"application/rss+xml\"/>",
+                                   xml_entity_escape(&href), rel))
+        .collect::<String>();
+    if let Some(hub) = meta.hub {
+        links.push_str(&format!("<atom:link href=\"{}\" rel=\"hub\"/>", xml_entity_escape(hub)));
+    }
+    if meta.archive.archived {
+        links.push_str("<fh:archive/>");
+    }
+    xml = xml.replacen("<channel>", &format!("<channel>{}", links), 1);
+
+    Ok(xml.into_bytes())
+}
+
+pub fn write(feed_directory: &Path, file: &str, meta: &FeedMeta, hints: &Hints, items: Vec<Item>) -> Result<()> {
+    let content = render(meta, hints, file, items)?;
+    write_atomic(&feed_directory.join(file), |out_file| {
+        out_file.extend_from_slice(&content);
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TITLE: &str = "Fix <T> & \"U\" in ]]> markers";
+    static BODY: &str = "<p>Use &lt;T&gt; & \"U\", not ]]> or <guid></p>";
+
+    fn issue() -> Issue {
+        Issue {
+            number: 1,
+            state: 0,
+            title: TITLE.to_owned(),
+            body: BODY.to_owned(),
+            body_zstd: None,
+            body_markdown: false,
+            user_login: String::from("a&b"),
+            html_url: String::from("https://example.com/issues/1"),
+            updated_at: 0,
+            created_at: None,
+            reactions: None,
+            closed_at: None
+        }
+    }
+
+    fn render_issue(text: bool) -> (String, Channel) {
+        let issue = issue();
+        let item = issue_to_item(&issue, &issue.html_url, &issue.html_url, &[ String::from("c++ & <d>") ],
+                                 Some(TITLE), None, text).unwrap();
+        let meta = FeedMeta {
+            title: String::from("Issues of a & b"),
+            subtitle: Some(String::from("<all> \"open\" issues")),
+            url: "https://example.com/issues?q=a&b",
+            icon: None,
+            logo: None,
+            author: None,
+            language: None,
+            rights: None,
+            public_url: None,
+            hub: None,
+            updated: Some(0),
+            archive: Default::default()
+        };
+        let xml = render(&meta, &Hints::default(), "rss.xml", vec![ item ]).unwrap();
+        let channel = Channel::read_from(&xml[..]).expect("Invalid RSS");
+        (String::from_utf8(xml).unwrap(), channel)
+    }
+
+    #[test]
+    fn escapes_html_once() {
+        let (xml, channel) = render_issue(false);
+        assert!(xml.contains("<title>Fix &lt;T&gt; &amp; &quot;U&quot; in ]]&gt; markers</title>"), "{}", xml);
+
+        assert_eq!(channel.title(), "Issues of a & b");
+        assert_eq!(channel.description(), "&lt;all&gt; &quot;open&quot; issues");
+        let item = &channel.items()[0];
+        assert_eq!(item.title(), Some(TITLE));
+        assert_eq!(item.description(), Some("Fix &lt;T&gt; &amp; &quot;U&quot; in ]]&gt; markers"));
+        // rss only reads back the last of the CDATA sections cdata_escape splits the content into
+        let content = &xml[xml.find("<content:encoded>").unwrap() + 17..xml.find("</content:encoded>").unwrap()];
+        assert_eq!(content.replace("]]><![CDATA[", ""), format!("<![CDATA[{}]]>", BODY));
+        assert_eq!(item.categories()[0].name(), "c++ & <d>");
+        assert_eq!(item.guid().map(Guid::value), Some("https://example.com/issues/1"));
+        assert!(xml.contains("<guid isPermaLink=\"true\">https://example.com/issues/1</guid>"), "{}", xml);
+    }
+
+    #[test]
+    fn escapes_text_once() {
+        let (_, channel) = render_issue(true);
+        assert_eq!(channel.items()[0].content(),
+                   Some("&lt;p&gt;Use &amp;lt;T&amp;gt; &amp; &quot;U&quot;, not ]]&gt; or &lt;guid&gt;&lt;/p&gt;"));
+    }
+}
@@ -0,0 +1,31 @@
+use std::{ fs, io::Write, path::Path };
+
+use anyhow::Result;
+use url::Url;
+
+use super::{ FeedSummary, root_url, file_url, xml_entity_escape, write_atomic };
+
+/// Write sitemap.xml, listing the `root_files` of the output directory and the `feed_files`
+/// of every generated feed for search engines
+pub fn write(out_path: &Path, feeds_url: &Url, root_files: &[&str], feed_files: &[&str],
+             feeds: &[FeedSummary]) -> Result<()> {
+    let mut urls = Vec::new();
+    for file in root_files {
+        urls.push(root_url(feeds_url).join(file)?);
+    }
+    for feed in feeds {
+        for file in feed_files {
+            urls.push(file_url(feeds_url, &feed.directory, file)?);
+        }
+    }
+
+    let mut sitemap = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+                                    <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
+    for url in urls {
+        sitemap.push_str(&format!("<url><loc>{}</loc></url>\n", xml_entity_escape(url.as_str())));
+    }
+    sitemap.push_str("</urlset>\n");
+
+    fs::create_dir_all(out_path)?;
+    write_atomic(&out_path.join("sitemap.xml"), |out| Ok(out.write_all(sitemap.as_bytes())?))
+}
@@ -0,0 +1,95 @@
+/// Elements separating words even without surrounding whitespace
+static BLOCKS: &[&str] = &[
+    "p", "br", "div", "li", "tr", "td", "th", "hr", "pre", "blockquote",
+    "h1", "h2", "h3", "h4", "h5", "h6"
+];
+
+/// Decode the entities ammonia writes when serializing sanitized HTML
+fn decode_entity(entity: &str) -> Option<char> {
+    match entity {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => {
+            let code = if entity.starts_with("#x") || entity.starts_with("#X") {
+                u32::from_str_radix(&entity[2..], 16).ok()
+            } else if entity.starts_with('#') {
+                entity[1..].parse().ok()
+            } else {
+                None
+            };
+            code.and_then(std::char::from_u32)
+        }
+    }
+}
+
+/// Text of sanitized HTML, with a line break for every block element
+fn lines(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find(|c| c == '<' || c == '&') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if rest.starts_with('<') {
+            let end = rest.find('>').map_or(rest.len(), |end| end + 1);
+            let name = rest[1..end].trim_start_matches('/')
+                .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            if BLOCKS.contains(&&name[..]) {
+                text.push('\n');
+            }
+            rest = &rest[end..];
+        } else {
+            let entity = rest.find(';')
+                .filter(|&end| end < 12)
+                .and_then(|end| decode_entity(&rest[1..end]).map(|c| (c, end)));
+            match entity {
+                Some((c, end)) => {
+                    text.push(c);
+                    rest = &rest[end + 1..];
+                },
+                None => {
+                    text.push('&');
+                    rest = &rest[1..];
+                }
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+/// Plain text of sanitized HTML, with whitespace collapsed
+fn text(html: &str) -> String {
+    lines(html).split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Plain text of sanitized HTML for --content-type text, a line per paragraph, list item or other block
+pub fn plain_text(html: &str) -> String {
+    lines(html).lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Plain text summary of the HTML `body`, cut at a word boundary before `length` characters
+pub fn summarize(body: &str, length: usize) -> String {
+    let text = text(body);
+    if text.chars().count() <= length {
+        return text
+    }
+
+    let cut = text.char_indices().nth(length).map_or(text.len(), |(i, _)| i);
+    let summary = match text[..cut].rfind(' ') {
+        _ if text[cut..].starts_with(' ') => &text[..cut],
+        Some(space) if space > 0 => &text[..space],
+        _ => &text[..cut]
+    };
+    format!("{}…", summary.trim_end_matches(|c: char| c.is_ascii_punctuation()))
+}
@@ -0,0 +1,116 @@
+use anyhow::{ Result, Context };
+use chrono::{ Utc, TimeZone };
+use serde::Serialize;
+use tinytemplate::TinyTemplate;
+
+use super::Issue;
+
+/// Values available to entry templates
+#[derive(Serialize)]
+struct EntryContext<'a> {
+    number: i64,
+    title: &'a str,
+    /// HTML, insert with {body | unescaped}
+    body: &'a str,
+    author: &'a str,
+    url: &'a str,
+    updated_at: String,
+    created_at: Option<String>,
+    /// The issue state followed by its labels
+    labels: &'a [String],
+    feed: &'a str
+}
+
+/// Values available to feed title templates
+#[derive(Serialize)]
+struct FeedContext<'a> {
+    name: &'a str,
+    repo: &'a str
+}
+
+/// User templates replacing entry titles, entry content and feed titles
+pub struct Templates<'t> {
+    /// Titles are plain text, so values aren't HTML-escaped
+    text: TinyTemplate<'t>,
+    html: TinyTemplate<'t>,
+    title: bool,
+    entry: bool,
+    feed: bool,
+    subtitle: bool
+}
+
+impl<'t> Templates<'t> {
+    pub fn new(title: Option<&'t str>, entry: Option<&'t str>, feed: Option<&'t str>, subtitle: Option<&'t str>) -> Result<Self> {
+        let mut text = TinyTemplate::new();
+        text.set_default_formatter(&tinytemplate::format_unescaped);
+        let mut html = TinyTemplate::new();
+
+        if let Some(title) = title {
+            text.add_template("title", title).context("Invalid title template")?;
+        }
+        if let Some(feed) = feed {
+            text.add_template("feed", feed).context("Invalid feed template")?;
+        }
+        if let Some(subtitle) = subtitle {
+            text.add_template("subtitle", subtitle).context("Invalid feed subtitle")?;
+        }
+        if let Some(entry) = entry {
+            html.add_template("entry", entry).context("Invalid entry template")?;
+        }
+
+        Ok(Templates {
+            text, html,
+            title: title.is_some(),
+            entry: entry.is_some(),
+            feed: feed.is_some(),
+            subtitle: subtitle.is_some()
+        })
+    }
+
+    /// Title and body of the entry for `issue`, with the templates applied
+    pub fn entry(&self, issue: &Issue, labels: &[String], feed: &str) -> Result<(String, String)> {
+        if !self.title && !self.entry {
+            return Ok((issue.title.clone(), issue.body.clone()))
+        }
+
+        let context = EntryContext {
+            number: issue.number,
+            title: &issue.title,
+            body: &issue.body,
+            author: &issue.user_login,
+            url: &issue.html_url,
+            updated_at: Utc.timestamp(issue.updated_at, 0).to_rfc3339(),
+            created_at: issue.created_at.map(|created_at| Utc.timestamp(created_at, 0).to_rfc3339()),
+            labels,
+            feed
+        };
+
+        let title = if self.title {
+            self.text.render("title", &context)
+                .with_context(|| format!("Failed to render title of #{}", issue.number))?
+        } else { issue.title.clone() };
+        let body = if self.entry {
+            self.html.render("entry", &context)
+                .with_context(|| format!("Failed to render entry of #{}", issue.number))?
+        } else { issue.body.clone() };
+        Ok((title, body))
+    }
+
+    /// Title of the feed `name` of `repo`
+    pub fn feed_title(&self, name: &str, repo: &str) -> Result<String> {
+        if !self.feed {
+            return Ok(name.to_owned())
+        }
+        Ok(self.text.render("feed", &FeedContext { name, repo })
+            .context("Failed to render feed title")?)
+    }
+
+    /// Subtitle of the feed `name` of `repo`, if any was given
+    pub fn feed_subtitle(&self, name: &str, repo: &str) -> Result<Option<String>> {
+        if !self.subtitle {
+            return Ok(None)
+        }
+        Ok(Some(self.text.render("subtitle", &FeedContext { name, repo })
+            .context("Failed to render feed subtitle")?))
+    }
+}
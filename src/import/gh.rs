@@ -0,0 +1,91 @@
+use std::path::Path;
+
+use serde::Deserialize;
+use anyhow::{ Result, Context };
+use tracing::{ info, debug };
+
+use crate::{
+    Conn,
+    import::open_lines,
+    query::{ self, repo_id, issues::IssueState, IssueRecord }
+};
+
+/// Fields requested from `gh issue list --json`
+pub static FIELDS: &str = "number,title,body,state,labels,author,assignees,url,updatedAt,createdAt";
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Issue {
+    number: i64,
+    title: String,
+    #[serde(default)]
+    body: String,
+    state: String,
+    #[serde(default)]
+    labels: Vec<Label>,
+    author: Option<Author>,
+    #[serde(default)]
+    assignees: Vec<Author>,
+    url: String,
+    updated_at: String,
+    created_at: Option<String>
+}
+
+#[derive(Deserialize)]
+struct Label {
+    name: String
+}
+
+#[derive(Deserialize)]
+struct Author {
+    login: String
+}
+
+pub async fn import(mut conn: &mut Conn, (ref owner, ref name): (String, String), path: &Path, compress_bodies: bool) -> Result<()> {
+    let repo = repo_id(&mut conn, owner, name).await?;
+
+    let issues: Vec<Issue> = serde_json::from_reader(open_lines(path)?)
+        .with_context(|| format!("Failed to read {}, expected output of gh issue list --json {}", path.display(), FIELDS))?;
+
+    let mut imported = 0;
+    for issue in issues {
+        debug!("#{}: {}", issue.number, issue.title);
+        let updated_at = chrono::DateTime::parse_from_rfc3339(&issue.updated_at)
+            .with_context(|| format!("Invalid timestamp on #{}", issue.number))?
+            .timestamp();
+        let created_at = issue.created_at.as_deref()
+            .map(chrono::DateTime::parse_from_rfc3339)
+            .transpose()
+            .with_context(|| format!("Invalid timestamp on #{}", issue.number))?
+            .map(|created_at| created_at.timestamp());
+
+        let record = IssueRecord {
+            number: issue.number,
+            state: IssueState::from_name(&issue.state.to_lowercase()).to_integer(),
+            title: issue.title,
+            body: issue.body,
+            body_markdown: true,
+            user_login: issue.author
+                .map(|author| author.login)
+                .unwrap_or_else(|| String::from("ghost")),
+            user_avatar_url: None,
+            html_url: issue.url,
+            updated_at,
+            created_at,
+            reactions: None,
+            comment_count: None,
+            labels: issue.labels.into_iter().map(|l| l.name).collect(),
+            assignees: issue.assignees.into_iter().map(|a| a.login).collect(),
+            comments: None,
+            closed_at: None,
+            milestone: None
+        };
+
+        if query::store_issue(&mut conn, repo, &record, compress_bodies).await? {
+            imported += 1;
+        }
+    }
+
+    info!("imported {} issues into {}/{}", imported, owner, name);
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use std::{ io::BufRead, path::PathBuf };
+
+use serde::Deserialize;
+use anyhow::{ Result, Context };
+use tracing::{ info, debug };
+
+use crate::{
+    Conn,
+    import::open_lines,
+    query::{ self, repo_id, issues::IssueState, IssueRecord }
+};
+
+// https://www.gharchive.org/ event dumps, one JSON event per line.
+// Only the fields needed to reconstruct issues are deserialized.
+
+#[derive(Deserialize)]
+struct Event {
+    #[serde(rename = "type")]
+    kind: String,
+    repo: EventRepo,
+    payload: serde_json::Value
+}
+
+#[derive(Deserialize)]
+struct EventRepo {
+    name: String
+}
+
+#[derive(Deserialize)]
+struct IssuePayload {
+    issue: Issue
+}
+
+#[derive(Deserialize)]
+struct Issue {
+    number: i64,
+    state: String,
+    title: String,
+    body: Option<String>,
+    user: Option<User>,
+    #[serde(default)]
+    assignees: Vec<User>,
+    html_url: String,
+    updated_at: String,
+    created_at: Option<String>,
+    reactions: Option<Reactions>,
+    comments: Option<i64>,
+    #[serde(default)]
+    labels: Vec<Label>,
+    pull_request: Option<serde_json::Value>
+}
+
+#[derive(Deserialize)]
+struct User {
+    login: String,
+    avatar_url: Option<String>
+}
+
+#[derive(Deserialize)]
+struct Reactions {
+    total_count: i64
+}
+
+#[derive(Deserialize)]
+struct Label {
+    name: String
+}
+
+pub async fn import(mut conn: &mut Conn, (ref owner, ref name): (String, String), files: &[PathBuf], compress_bodies: bool) -> Result<()> {
+    let repo = repo_id(&mut conn, owner, name).await?;
+    let full_name = format!("{}/{}", owner, name);
+
+    let mut imported = 0;
+    for path in files {
+        info!("importing {}", path.display());
+        let lines = open_lines(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+
+        for line in lines.lines() {
+            let line = line?;
+            if line.trim().is_empty() { continue }
+
+            let event: Event = serde_json::from_str(&line)
+                .with_context(|| format!("Invalid event in {}", path.display()))?;
+            if !event.repo.name.eq_ignore_ascii_case(&full_name) { continue }
+            if event.kind != "IssuesEvent" && event.kind != "IssueCommentEvent" { continue }
+
+            let issue = match serde_json::from_value::<IssuePayload>(event.payload) {
+                Ok(payload) => payload.issue,
+                Err(e) => {
+                    debug!("skipping {} without issue: {}", event.kind, e);
+                    continue
+                }
+            };
+            // Comments on pull requests are IssueCommentEvents too
+            if issue.pull_request.is_some() { continue }
+
+            let state = IssueState::from_name(&issue.state);
+            let updated_at = chrono::DateTime::parse_from_rfc3339(&issue.updated_at)
+                .with_context(|| format!("Invalid timestamp on #{}", issue.number))?
+                .timestamp();
+            let created_at = issue.created_at.as_deref()
+                .map(chrono::DateTime::parse_from_rfc3339)
+                .transpose()
+                .with_context(|| format!("Invalid timestamp on #{}", issue.number))?
+                .map(|created_at| created_at.timestamp());
+
+            let record = IssueRecord {
+                number: issue.number,
+                state: state.to_integer(),
+                title: issue.title,
+                body: issue.body.unwrap_or_default(),
+                body_markdown: true,
+                user_avatar_url: issue.user.as_ref().and_then(|user| user.avatar_url.clone()),
+                user_login: issue.user
+                    .map(|user| user.login)
+                    .unwrap_or_else(|| String::from("ghost")),
+                html_url: issue.html_url,
+                updated_at,
+                created_at,
+                reactions: issue.reactions.map(|reactions| reactions.total_count),
+                comment_count: issue.comments,
+                labels: issue.labels.into_iter().map(|l| l.name).collect(),
+                assignees: issue.assignees.into_iter().map(|user| user.login).collect(),
+                comments: None,
+                closed_at: None,
+                milestone: None
+            };
+
+            debug!("#{}: {}", record.number, record.title);
+            if query::store_issue(&mut conn, repo, &record, compress_bodies).await? {
+                imported += 1;
+            }
+        }
+    }
+
+    info!("imported {} issue events into {}", imported, full_name);
+    Ok(())
+}
@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+
+use reqwest::Client;
+use serde::Deserialize;
+use anyhow::{ anyhow, Result, Context };
+use url::Url;
+
+use tracing::{ info, debug };
+
+use crate::{
+    Conn,
+    backend::{ Backend, with_segments },
+    query::{ self, repo_id, rest, issues::IssueState, IssueRecord }
+};
+
+// https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-search/
+// Jira Cloud only serves /rest/api/3/search/jql, paged by token, while Jira Server and
+// Data Center only have /rest/api/2/search, paged by offset.
+// Every Jira project becomes a repository <site host>/<project key>,
+// labels and components both become labels.
+
+static PAGE_SIZE: usize = 100;
+static FIELDS: &str = "summary,description,status,labels,components,reporter,assignee,created,updated,votes,project";
+
+/// Results of either search endpoint, see `Paging`
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchResults {
+    #[serde(default)]
+    start_at: usize,
+    total: Option<usize>,
+    next_page_token: Option<String>,
+    issues: Vec<Issue>
+}
+
+enum Paging {
+    /// Jira Cloud, continuing from the token of the last page
+    Token(Option<String>),
+    /// Jira Server and Data Center, continuing at an offset
+    Offset(usize)
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Issue {
+    key: String,
+    fields: Fields,
+    rendered_fields: Option<RenderedFields>
+}
+
+#[derive(Deserialize)]
+struct Fields {
+    summary: String,
+    /// Wiki markup text from Jira Server, an Atlassian Document Format tree from Jira Cloud
+    description: Option<serde_json::Value>,
+    status: Status,
+    #[serde(default)]
+    labels: Vec<String>,
+    #[serde(default)]
+    components: Vec<Named>,
+    reporter: Option<User>,
+    assignee: Option<User>,
+    created: String,
+    updated: String,
+    votes: Option<Votes>,
+    project: Project
+}
+
+#[derive(Deserialize)]
+struct RenderedFields {
+    description: Option<String>
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Status {
+    status_category: StatusCategory
+}
+
+#[derive(Deserialize)]
+struct StatusCategory {
+    key: String
+}
+
+#[derive(Deserialize)]
+struct Votes {
+    votes: i64
+}
+
+#[derive(Deserialize)]
+struct Named {
+    name: String
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct User {
+    display_name: String,
+    #[serde(default)]
+    avatar_urls: HashMap<String, String>
+}
+
+#[derive(Deserialize)]
+struct Project {
+    key: String
+}
+
+pub async fn import(mut conn: &mut Conn, base_url: &Url, auth: &rest::Auth, jql: &str, compress_bodies: bool) -> Result<()> {
+    let client = Client::new();
+    let site = base_url.host_str()
+        .ok_or_else(|| anyhow!("Jira URL without host: {}", base_url))?
+        .to_owned();
+    let mut paging = if site.ends_with(".atlassian.net") { Paging::Token(None) } else { Paging::Offset(0) };
+    let search_url = match paging {
+        Paging::Token(_) => with_segments(base_url, &[ "rest", "api", "3", "search", "jql" ]),
+        Paging::Offset(_) => with_segments(base_url, &[ "rest", "api", "2", "search" ])
+    };
+
+    let mut repos = HashMap::new();
+    let mut imported = 0;
+    loop {
+        let mut url = search_url.clone();
+        url.query_pairs_mut()
+            .append_pair("jql", jql)
+            .append_pair("fields", FIELDS)
+            .append_pair("expand", "renderedFields")
+            .append_pair("maxResults", &PAGE_SIZE.to_string());
+        match &paging {
+            Paging::Token(Some(token)) => { url.query_pairs_mut().append_pair("nextPageToken", token); },
+            Paging::Token(None) => (),
+            Paging::Offset(start_at) => { url.query_pairs_mut().append_pair("startAt", &start_at.to_string()); }
+        }
+
+        let page: SearchResults = rest::get(&client, auth, &url).await?
+            .json().await?;
+        debug!("{}..{} of {:?}", imported, imported + page.issues.len(), page.total);
+
+        imported += page.issues.len();
+        let has_next_page = !page.issues.is_empty() && match &mut paging {
+            Paging::Token(token) => {
+                *token = page.next_page_token;
+                token.is_some()
+            },
+            Paging::Offset(start_at) => {
+                *start_at = page.start_at + page.issues.len();
+                page.total.map_or(false, |total| *start_at < total)
+            }
+        };
+
+        for issue in page.issues {
+            let key = issue.key;
+            let project = issue.fields.project.key;
+            let repo = match repos.get(&project) {
+                Some(&repo) => repo,
+                None => {
+                    let repo = repo_id(&mut conn, &site, &project).await?;
+                    query::set_backend(&mut conn, repo, Backend::Jira, base_url).await?;
+                    repos.insert(project.clone(), repo);
+                    repo
+                }
+            };
+
+            // Issue keys are <project>-<number>
+            let number = key
+                .rsplit('-')
+                .next()
+                .and_then(|n| n.parse().ok())
+                .ok_or_else(|| anyhow!("Unexpected issue key {}", key))?;
+            let state = match &issue.fields.status.status_category.key[..] {
+                "done" => IssueState::CLOSED,
+                _ => IssueState::OPEN
+            };
+            let updated_at = chrono::DateTime::parse_from_str(&issue.fields.updated, "%Y-%m-%dT%H:%M:%S%.f%z")
+                .with_context(|| format!("Invalid timestamp on {}", key))?
+                .timestamp();
+            let created_at = chrono::DateTime::parse_from_str(&issue.fields.created, "%Y-%m-%dT%H:%M:%S%.f%z")
+                .with_context(|| format!("Invalid timestamp on {}", key))?
+                .timestamp();
+            let description = match issue.fields.description {
+                Some(serde_json::Value::String(description)) => Some(description),
+                _ => None
+            };
+            let body = issue.rendered_fields
+                .and_then(|rendered| rendered.description)
+                .or(description)
+                .unwrap_or_default();
+
+            debug!("{}: {}", key, issue.fields.summary);
+            query::store_issue(&mut conn, repo, &IssueRecord {
+                number,
+                state: state.to_integer(),
+                title: issue.fields.summary,
+                body,
+                body_markdown: false,
+                user_avatar_url: issue.fields.reporter.as_ref()
+                    .and_then(|reporter| reporter.avatar_urls.get("48x48").cloned()),
+                user_login: issue.fields.reporter
+                    .map(|reporter| reporter.display_name)
+                    .unwrap_or_else(|| String::from("anonymous")),
+                html_url: with_segments(base_url, &[ "browse", &key ]).into_string(),
+                updated_at,
+                created_at: Some(created_at),
+                reactions: issue.fields.votes.map(|votes| votes.votes),
+                comment_count: None,
+                labels: issue.fields.labels.into_iter()
+                    .chain(issue.fields.components.into_iter().map(|c| c.name))
+                    .collect(),
+                assignees: issue.fields.assignee
+                    .map(|assignee| assignee.display_name)
+                    .into_iter()
+                    .collect(),
+                comments: None,
+                closed_at: None,
+                milestone: None
+            }, compress_bodies).await?;
+        }
+
+        if !has_next_page { break }
+    }
+
+    info!("imported {} issues from {} projects", imported, repos.len());
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use std::{
+    fs::File,
+    io::{ self, BufRead, BufReader },
+    path::Path
+};
+
+use flate2::read::MultiGzDecoder;
+
+pub mod gharchive;
+pub mod gh;
+pub mod jira;
+pub mod records;
+
+/// Open `path` for line-wise reading, transparently decompressing `.gz` files.
+/// A path of `-` reads from stdin.
+pub fn open_lines(path: &Path) -> io::Result<Box<dyn BufRead>> {
+    if path == Path::new("-") {
+        return Ok(Box::new(BufReader::new(io::stdin())))
+    }
+
+    let file = File::open(path)?;
+    Ok(match path.extension().and_then(|ext| ext.to_str()) {
+        Some("gz") => Box::new(BufReader::new(MultiGzDecoder::new(file))),
+        _ => Box::new(BufReader::new(file))
+    })
+}
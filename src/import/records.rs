@@ -0,0 +1,257 @@
+use std::{ io::BufRead, path::Path };
+
+use serde::{ Deserialize, Serialize };
+use anyhow::{ anyhow, Result, Context };
+use url::Url;
+use tracing::{ info, debug };
+
+use crate::{
+    parse_repo,
+    Conn,
+    backend::Backend,
+    import::open_lines,
+    query::{ self, repo_id, issues::IssueState, IssueRecord }
+};
+
+/// Tracker-independent issue format accepted by `import json` and `import csv`,
+/// and written by `export`. See the README for a description of every field.
+#[derive(Deserialize, Serialize)]
+pub struct Record {
+    pub number: i64,
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+    /// "html" or "markdown", HTML if omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_format: Option<String>,
+    /// "open" or "closed"
+    pub state: String,
+    /// RFC 3339 timestamp
+    pub updated_at: String,
+    /// RFC 3339 timestamp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reactions: Option<i64>,
+    pub author: Option<String>,
+    pub url: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Logins of assigned users
+    #[serde(default)]
+    pub assignees: Vec<String>,
+    /// owner/name, written by `export` so that `import export` can restore several repositories
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>
+}
+
+/// CSV has no lists, so labels and assignees are joined by commas within a single column
+#[derive(Deserialize, Serialize)]
+pub struct CsvRecord {
+    number: i64,
+    title: String,
+    #[serde(default)]
+    body: String,
+    #[serde(default)]
+    body_format: Option<String>,
+    state: String,
+    updated_at: String,
+    #[serde(default)]
+    created_at: Option<String>,
+    #[serde(default)]
+    reactions: Option<i64>,
+    author: Option<String>,
+    url: Option<String>,
+    #[serde(default)]
+    labels: String,
+    #[serde(default)]
+    assignees: String
+}
+
+fn split_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+impl From<CsvRecord> for Record {
+    fn from(record: CsvRecord) -> Record {
+        Record {
+            number: record.number,
+            title: record.title,
+            body: record.body,
+            body_format: record.body_format,
+            state: record.state,
+            updated_at: record.updated_at,
+            created_at: record.created_at,
+            reactions: record.reactions,
+            author: record.author,
+            url: record.url,
+            labels: split_list(&record.labels),
+            assignees: split_list(&record.assignees),
+            repo: None,
+            backend: None,
+            base_url: None
+        }
+    }
+}
+
+impl From<Record> for CsvRecord {
+    fn from(record: Record) -> CsvRecord {
+        CsvRecord {
+            number: record.number,
+            title: record.title,
+            body: record.body,
+            body_format: record.body_format,
+            state: record.state,
+            updated_at: record.updated_at,
+            created_at: record.created_at,
+            reactions: record.reactions,
+            author: record.author,
+            url: record.url,
+            labels: record.labels.join(","),
+            assignees: record.assignees.join(",")
+        }
+    }
+}
+
+impl Record {
+    pub fn into_issue_record(self) -> Result<IssueRecord> {
+        let updated_at = chrono::DateTime::parse_from_rfc3339(&self.updated_at)
+            .with_context(|| format!("Invalid updated_at on #{}", self.number))?
+            .timestamp();
+        let created_at = self.created_at.as_deref()
+            .map(chrono::DateTime::parse_from_rfc3339)
+            .transpose()
+            .with_context(|| format!("Invalid created_at on #{}", self.number))?
+            .map(|created_at| created_at.timestamp());
+        let body_markdown = match self.body_format.as_deref() {
+            None | Some("") | Some("html") => false,
+            Some("markdown") => true,
+            Some(format) => return Err(anyhow!("Invalid body_format {} on #{}, expected html or markdown",
+                                               format, self.number))
+        };
+
+        Ok(IssueRecord {
+            number: self.number,
+            state: IssueState::from_name(&self.state).to_integer(),
+            title: self.title,
+            body: self.body,
+            body_markdown,
+            user_login: self.author.unwrap_or_else(|| String::from("ghost")),
+            user_avatar_url: None,
+            html_url: self.url.unwrap_or_default(),
+            updated_at,
+            created_at,
+            reactions: self.reactions,
+            comment_count: None,
+            labels: self.labels,
+            assignees: self.assignees,
+            comments: None,
+            closed_at: None,
+            milestone: None
+        })
+    }
+}
+
+/// Parse either a JSON array of records, or one record per line
+fn read_json(path: &Path) -> Result<Vec<Record>> {
+    let mut reader = open_lines(path)?;
+    let first = loop {
+        let buf = reader.fill_buf()?;
+        match buf.iter().position(|b| !b.is_ascii_whitespace()) {
+            Some(i) => break Some(buf[i]),
+            None if buf.is_empty() => break None,
+            None => {
+                let len = buf.len();
+                reader.consume(len);
+            }
+        }
+    };
+
+    if first == Some(b'[') {
+        return Ok(serde_json::from_reader(reader)?)
+    }
+
+    reader.lines()
+        .filter(|line| line.as_ref().map(|l| !l.trim().is_empty()).unwrap_or(true))
+        .enumerate()
+        .map(|(i, line)| serde_json::from_str(&line?)
+             .with_context(|| format!("Invalid record on line {}", i + 1)))
+        .collect()
+}
+
+fn read_csv(path: &Path) -> Result<Vec<Record>> {
+    csv::Reader::from_reader(open_lines(path)?)
+        .deserialize::<CsvRecord>()
+        .map(|record| Ok(record?.into()))
+        .collect()
+}
+
+async fn store(conn: &mut Conn, (ref owner, ref name): (String, String), records: Vec<Record>, compress_bodies: bool) -> Result<i64> {
+    let repo = repo_id(&mut *conn, owner, name).await?;
+
+    let mut imported = 0;
+    for record in records {
+        debug!("#{}: {}", record.number, record.title);
+        if query::store_issue(&mut *conn, repo, &record.into_issue_record()?, compress_bodies).await? {
+            imported += 1;
+        }
+    }
+
+    info!("imported {} issues into {}/{}", imported, owner, name);
+    Ok(repo)
+}
+
+pub async fn import_json(conn: &mut Conn, repo: (String, String), path: &Path, compress_bodies: bool) -> Result<()> {
+    let records = read_json(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    store(conn, repo, records, compress_bodies).await?;
+    Ok(())
+}
+
+pub async fn import_csv(conn: &mut Conn, repo: (String, String), path: &Path, compress_bodies: bool) -> Result<()> {
+    let records = read_csv(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    store(conn, repo, records, compress_bodies).await?;
+    Ok(())
+}
+
+/// Restore the output of `export`, which names the repository of every record
+pub async fn import_export(conn: &mut Conn, path: &Path, compress_bodies: bool) -> Result<()> {
+    let records = read_json(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+
+    // Keep repositories in the order they were exported
+    let mut repos: Vec<(String, Vec<Record>)> = Vec::new();
+    for record in records {
+        let name = record.repo.clone()
+            .ok_or_else(|| anyhow!("Record #{} names no repository, use `import json <repo>` instead", record.number))?;
+        match repos.iter_mut().find(|(repo, _)| *repo == name) {
+            Some((_, records)) => records.push(record),
+            None => repos.push((name, vec![ record ]))
+        }
+    }
+
+    for (name, records) in repos {
+        let (owner, name) = parse_repo(&name)?;
+        // Stored first, as the backend determines the profile URLs of authors
+        if let Some(backend) = &records[0].backend {
+            let backend: Backend = backend.parse()?;
+            let base_url = match &records[0].base_url {
+                Some(url) => Url::parse(url)?,
+                None => backend.default_base_url()
+            };
+            let repo = repo_id(&mut *conn, &owner, &name).await?;
+            query::set_backend(&mut *conn, repo, backend, &base_url).await?;
+        }
+        store(&mut *conn, (owner, name), records, compress_bodies).await?;
+    }
+    Ok(())
+}
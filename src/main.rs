@@ -1,7 +1,7 @@
-use std::{ env, io, path::PathBuf };
+use std::{ env, io, path::{ Path, PathBuf } };
 use structopt::StructOpt;
-use sqlx::SqlitePool;
-use tracing::info;
+use url::Url;
+use tracing::{ info, warn };
 use tracing_subscriber::{
     fmt, filter,
     layer::SubscriberExt,
@@ -9,110 +9,553 @@ use tracing_subscriber::{
 };
 
 use anyhow::{ anyhow, Result, Context };
+use chrono::{ DateTime, NaiveDate, Utc };
 
+pub mod backend;
+pub mod db;
 pub mod query;
 pub mod generate;
+pub mod import;
+pub mod export;
+pub mod stats;
+pub mod search;
+
+use backend::Backend;
 
 #[derive(StructOpt)]
 #[structopt(name = "github-label-feed")]
 struct Opt {
+    #[structopt(flatten)]
+    db: db::DbOpts,
     #[structopt(subcommand)]
     mode: OptMode,
 }
 
-#[derive(StructOpt)]
+#[derive(StructOpt, Clone)]
+pub struct SyncOpts {
+    repo: String,
+    /// Forge hosting <repo>: github, gitlab, gitea (also for Forgejo), bitbucket or sourcehut
+    #[structopt(long, default_value = "github")]
+    backend: Backend,
+    /// Web root of a self-hosted forge instance, e.g. https://gitlab.example.org.
+    /// Gitea defaults to https://codeberg.org
+    #[structopt(long)]
+    base_url: Option<Url>,
+    /// Only synchronise issues carrying any of these labels (GitHub only)
+    #[structopt(long = "label", number_of_values = 1)]
+    labels: Vec<String>,
+    /// Also store the last <comments> comments of every updated issue, at most 100, for generate --comments (GitHub only)
+    #[structopt(long, default_value = "0", parse(try_from_str = parse_comments))]
+    comments: u32,
+
+    #[structopt(long = "github-api-token", env = "GITHUB_TOKEN", hide_env_values = true)]
+    github_api_token: Option<String>,
+    /// Only needed to access private GitLab projects
+    #[structopt(long = "gitlab-api-token", env = "GITLAB_TOKEN", hide_env_values = true)]
+    gitlab_api_token: Option<String>,
+    /// Only needed to access private Gitea/Forgejo repositories
+    #[structopt(long = "gitea-api-token", env = "GITEA_TOKEN", hide_env_values = true)]
+    gitea_api_token: Option<String>,
+    /// Repository or workspace access token, only needed for private Bitbucket repositories
+    #[structopt(long = "bitbucket-api-token", env = "BITBUCKET_TOKEN", hide_env_values = true)]
+    bitbucket_api_token: Option<String>,
+    /// Personal access token with todo.sr.ht read access
+    #[structopt(long = "sourcehut-api-token", env = "SRHT_TOKEN", hide_env_values = true)]
+    sourcehut_api_token: Option<String>,
+
+    #[structopt(subcommand)]
+    then: Option<AfterSync>
+}
+
+#[derive(StructOpt, Clone)]
+enum AfterSync {
+    /// Generate feeds right after synchronising, needed to use the data of --db :memory:
+    Generate(GenerateOpts)
+}
+
+#[derive(StructOpt, Clone)]
 pub struct GenerateOpts {
     /// Repository to generate feeds for
-    repo: String,
+    #[structopt(required_unless_one = &["config", "all"])]
+    repo: Option<String>,
     /// Root directory of output
-    out_path: PathBuf,
-    /// Labels for which to generate feeds. Leave empty to select all labels
+    #[structopt(required_unless_one = &["config", "stdout", "all"])]
+    out_path: Option<PathBuf>,
+    /// Labels, or logins with --by assignee|author, for which to generate feeds. Leave empty to select all.
+    /// Names containing * or ? are globs matching any of the known names, unless they are known names themselves,
+    /// labels joined by + select the issues carrying all of them, unless the name is a label itself
     labels: Vec<String>,
+    /// Label or login to generate a feed for, like the positional names but usable without an output directory
+    #[structopt(long, number_of_values = 1)]
+    label: Vec<String>,
+    /// Text query to generate a feed of the matching issues for, like "panic OR segfault", named after the query.
+    /// Words and GitHub search qualifiers like label:bug are required, except those prefixed with -,
+    /// alternatives separated by OR
+    #[structopt(long, number_of_values = 1)]
+    query: Vec<String>,
+    /// Title of a milestone to generate a feed of its issues for, named after the milestone, along with
+    /// milestone.html showing its progress. Milestones are stored by GitHub sync
+    #[structopt(long, number_of_values = 1)]
+    milestone: Vec<String>,
+    /// Generate the feed and milestone.html of every milestone
+    #[structopt(long)]
+    milestones: bool,
+    /// Also generate feeds for all known names matching this regular expression
+    #[structopt(long, number_of_values = 1)]
+    regex: Vec<regex::Regex>,
+    /// Also generate the feed _unlabeled of issues without any label
+    #[structopt(long)]
+    include_unlabeled: bool,
+    /// Generate a feed per label, per author, or per assignee plus one of unassigned issues
+    #[structopt(long, default_value = "label", possible_values = generate::GROUPINGS)]
+    by: String,
+    /// Directory names of the feeds: slashes and whitespace replaced by _, percent-encoded,
+    /// or transliterated to lowercase ASCII words
+    #[structopt(long, default_value = "underscore", possible_values = generate::SLUGS)]
+    slug: String,
+    /// Write the feed of a label or login to this directory instead, as <name>=<directory>,
+    /// e.g. to keep published URLs when a label is renamed
+    #[structopt(long, number_of_values = 1, parse(try_from_str = parse_mapping))]
+    directory: Vec<(String, String)>,
+    /// Make directory names valid on Windows, replacing forbidden characters and trailing dots
+    /// and detecting names only differing in case
+    #[structopt(long)]
+    portable_paths: bool,
     /// Exclude open issues from the feeds
     #[structopt(long)]
     without_open: bool,
     /// Exclude closed issues from the feeds
     #[structopt(long)]
     without_closed: bool,
+    /// Leave out feeds without any issues after filtering, instead of writing empty feeds
+    #[structopt(long)]
+    skip_empty: bool,
+    /// Keep only the most recently updated issues in each feed, 0 keeps all
+    #[structopt(long, default_value = "100")]
+    max_entries: u32,
+    /// Only include issues updated at or after this date or RFC 3339 timestamp
+    #[structopt(long, parse(try_from_str = parse_time))]
+    updated_since: Option<DateTime<Utc>>,
+    /// Only include issues updated before this date or RFC 3339 timestamp
+    #[structopt(long, parse(try_from_str = parse_time))]
+    updated_before: Option<DateTime<Utc>>,
+    /// Show issues with any activity, or only newly opened ones, dated and limited by their creation time.
+    /// --updated-since and --updated-before then apply to the creation time as well
+    #[structopt(long, default_value = "updated", possible_values = generate::MODES)]
+    mode: String,
+    /// Collapse the activity of each day or week into a single entry listing the opened, closed and updated issues
+    #[structopt(long, possible_values = generate::PERIODS, conflicts_with_all = &["split-states", "page-size"])]
+    digest: Option<String>,
+    /// Only include issues with at least this many reactions, votes or upvotes, whichever the forge counts
+    #[structopt(long, default_value = "0")]
+    min_reactions: u32,
+    /// Only include issues with at least this many comments
+    #[structopt(long, default_value = "0")]
+    min_comments: u32,
+    /// Leave out issues by authors matching this glob, like dependabot* or renovate[bot], ignoring ASCII case.
+    /// Can be given several times
+    #[structopt(long, number_of_values = 1)]
+    exclude_author: Vec<String>,
+    /// Order of the feed entries. Issues without creation time or reactions come last
+    #[structopt(long, default_value = "number", possible_values = generate::SORT_KEYS)]
+    sort: String,
+    /// Sort descending or ascending
+    #[structopt(long, default_value = "desc", possible_values = generate::ORDERS)]
+    order: String,
 
-    /// Generate an RSS feed to rss.xml
+    /// Generate an RSS feed to rss.xml, or --rss-file
     #[structopt(long)]
     rss: bool,
-    /// Generate an Atom feed to atom.xml
+    /// Generate an Atom feed to atom.xml, or --atom-file
+    #[structopt(long)]
+    atom: bool,
+    /// Generate a JSON Feed to feed.json, or --json-feed-file
+    #[structopt(long)]
+    json_feed: bool,
+    /// File name of RSS feeds
+    #[structopt(long, default_value = "rss.xml")]
+    rss_file: String,
+    /// Minutes RSS readers may cache the feeds before polling again, e.g. the interval of syncs
+    #[structopt(long)]
+    rss_ttl: Option<u32>,
+    /// Comma-separated hours (0 to 23, GMT) during which RSS readers shouldn't poll, e.g. when no sync runs
+    #[structopt(long, use_delimiter = true)]
+    rss_skip_hours: Vec<u8>,
+    /// Comma-separated days, like Saturday,Sunday, on which RSS readers shouldn't poll
+    #[structopt(long, use_delimiter = true, possible_values = generate::DAYS)]
+    rss_skip_days: Vec<String>,
+    /// File name of Atom feeds
+    #[structopt(long, default_value = "atom.xml")]
+    atom_file: String,
+    /// File name of JSON Feeds
+    #[structopt(long, default_value = "feed.json")]
+    json_feed_file: String,
+    /// Template of entry titles, e.g. "[{feed}] #{number}: {title}". See the README for all values
+    #[structopt(long)]
+    title_template: Option<String>,
+    /// File with a template of the entry content, HTML-escaping values unless | unescaped
+    #[structopt(long)]
+    entry_template: Option<PathBuf>,
+    /// Title of the feeds instead of the bare name, a template with the values {name} and {repo}
+    #[structopt(long)]
+    feed_template: Option<String>,
+    /// Subtitle or description of the feeds, a template like --feed-template
+    #[structopt(long)]
+    feed_subtitle: Option<String>,
+    /// URL of a small icon of the feeds
+    #[structopt(long)]
+    feed_icon: Option<String>,
+    /// URL of a larger logo of the feeds
+    #[structopt(long)]
+    feed_logo: Option<String>,
+    /// Use the avatar of the repository owner as icon and logo, unless set by --feed-icon or --feed-logo. GitHub only
+    #[structopt(long)]
+    owner_avatar: bool,
+    /// Name of the author of the feeds
+    #[structopt(long)]
+    feed_author: Option<String>,
+    /// Language of the feeds, a language tag like en-US
     #[structopt(long)]
-    atom: bool
+    feed_language: Option<String>,
+    /// Copyright notice of the feeds
+    #[structopt(long)]
+    feed_rights: Option<String>,
+    /// Generate every output described in this TOML file instead, see the README
+    #[structopt(long, conflicts_with = "repo")]
+    config: Option<PathBuf>,
+    /// Write the feeds to <out_path>/<label>, or to <out_path>/<owner>/<name>/<label> for several repositories
+    /// sharing an output directory. --base-url is the public URL of <out_path> either way
+    #[structopt(long, default_value = "flat", possible_values = generate::LAYOUTS)]
+    layout: String,
+    /// Generate the feeds of every stored repository instead, to <all>/<owner>/<name>
+    #[structopt(long, conflicts_with_all = &["repo", "out-path", "config", "archive"])]
+    all: Option<PathBuf>,
+    /// Write index.html pages listing the feeds and their issues
+    #[structopt(long)]
+    html_index: bool,
+    /// Write issues.json of every feed, its issues as plain JSON data for custom frontends
+    #[structopt(long)]
+    issues_json: bool,
+    /// Write badge.json of every feed, a shields.io endpoint showing the number of open issues
+    #[structopt(long)]
+    badge_json: bool,
+    /// Write feeds.opml listing all generated feeds, requires --base-url
+    #[structopt(long)]
+    opml: bool,
+    /// Write sitemap.xml listing the index pages and feeds, requires --base-url
+    #[structopt(long)]
+    sitemap: bool,
+    /// Public URL under which the output directory is served, for links of the feeds to themselves
+    #[structopt(long = "base-url")]
+    feeds_url: Option<Url>,
+    /// WebSub hub announced in the feeds, for push updates after pinging it with the changed feeds
+    #[structopt(long)]
+    hub_url: Option<Url>,
+    /// Delete the directories of feeds generated by earlier runs that aren't generated anymore.
+    /// Only without a restricted selection of names, queries or milestones
+    #[structopt(long)]
+    prune_output: bool,
+    /// Rewrite every feed, even if its entries didn't change since the last run
+    #[structopt(long)]
+    force: bool,
+    /// Print which feeds would be written and how many issues each has, without writing anything
+    #[structopt(long, conflicts_with = "stdout")]
+    dry_run: bool,
+    /// Write a JSON report of the run, with the files written and the issues of every feed, to this file, or to stdout if -
+    #[structopt(long, conflicts_with = "stdout")]
+    report: Option<PathBuf>,
+    /// Date feeds by their newest entry instead of the current time, for byte-identical output of unchanged data
+    #[structopt(long)]
+    deterministic: bool,
+    /// Show the state and labels of issues as colored badges above the entry content
+    #[structopt(long)]
+    badges: bool,
+    /// Append up to this many of the most recent comments to the entry content, as stored by sync --comments
+    #[structopt(long, default_value = "0")]
+    comments: u32,
+    /// Add a plain text summary of up to this many characters of the body to every entry
+    #[structopt(long)]
+    summary_length: Option<usize>,
+    /// Entry content as sanitized HTML, or converted to plain text for readers rendering HTML poorly
+    #[structopt(long, default_value = "html", possible_values = generate::CONTENT_TYPES)]
+    content_type: String,
+    /// Identify entries by the issue URL, or by a tag: URI of the forge, the creation date and the issue number,
+    /// which survives changes of the URL. Switching makes readers show every entry again
+    #[structopt(long, default_value = "url", possible_values = generate::ENTRY_IDS)]
+    entry_ids: String,
+    /// Leave out the entry content, keeping only the summary
+    #[structopt(long, requires = "summary-length")]
+    without_content: bool,
+    /// Also write gzip-compressed copies of all files as <file>.gz
+    #[structopt(long)]
+    gzip: bool,
+    /// Also write brotli-compressed copies of all files as <file>.br
+    #[structopt(long)]
+    brotli: bool,
+    /// Afterwards pack the output directory into this .tar, .tar.gz or .tgz file
+    #[structopt(long)]
+    archive: Option<PathBuf>,
+    /// Also write feeds of only the open and only the closed issues next to every feed file,
+    /// named after the state and the feed file, e.g. open.atom.xml and closed.atom.xml for atom.xml
+    #[structopt(long)]
+    split_states: bool,
+    /// Write milestones.ics, a calendar of the due dates of milestones, as stored by GitHub sync
+    #[structopt(long)]
+    ics: bool,
+    /// Also add the closing of the issues in the generated feeds to milestones.ics
+    #[structopt(long, requires = "ics")]
+    ics_closed: bool,
+    /// Write issues/<number>.html, a page of every issue of the feeds
+    #[structopt(long)]
+    issue_pages: bool,
+    /// Link the entries to the pages of --issue-pages instead of the forge, for self-contained mirrors
+    #[structopt(long, requires = "issue-pages")]
+    link_local: bool,
+    /// Also deliver every issue of the feeds to this Maildir, once per update, for reading in mail clients
+    #[structopt(long)]
+    maildir: Option<PathBuf>,
+    /// Split feeds into pages of this many entries, archiving older ones in RFC 5005 archives archive-1.atom.xml,
+    /// archive-2.atom.xml, … next to the feed file, numbered from the oldest. Combine with --max-entries 0
+    #[structopt(long)]
+    page_size: Option<usize>,
+    /// Generate up to this many feeds at once, each on a database connection of its own
+    #[structopt(long, default_value = "4")]
+    jobs: usize,
+    /// Write the single selected feed to stdout instead of an output directory, needs exactly one format
+    #[structopt(long, conflicts_with_all = &["out-path", "config", "html-index", "issues-json", "badge-json", "opml", "sitemap", "archive", "gzip", "brotli",
+                                              "split-states", "ics", "page-size", "prune-output", "maildir", "issue-pages", "all"])]
+    stdout: bool
 }
 
-#[derive(StructOpt)]
+#[derive(StructOpt, Clone)]
+pub struct ExportOpts {
+    /// Repository to export, all repositories if omitted
+    repo: Option<String>,
+    /// ndjson writes one record per line, both formats can be imported again
+    #[structopt(long, default_value = "ndjson", possible_values = export::FORMATS)]
+    format: String,
+    /// File to write to instead of stdout
+    #[structopt(long)]
+    out: Option<PathBuf>
+}
+
+#[derive(StructOpt, Clone)]
 enum OptMode {
     /// List repositories currently stored in database
     List,
     /// Synchronise <repo> updates, starting from most recent issue update time
-    Sync {
+    Sync(SyncOpts),
+    /// Generate Atom feeds for <repo>
+    Generate(GenerateOpts),
+    /// Import issues from sources other than the GitHub API
+    Import(ImportSource),
+    /// Write the stored issues of [repo] with their labels to stdout
+    Export(ExportOpts),
+    /// Show issue and label counts of [repo], or of all repositories
+    Stats {
+        repo: Option<String>
+    },
+    /// Delete <repo> with all of its issues and labels
+    Remove {
         repo: String,
-        #[structopt(long = "github-api-token", env = "GITHUB_TOKEN", hide_env_values = true)]
-        github_api_token: String
+        /// Confirm the deletion, without it only the affected data is shown
+        #[structopt(long)]
+        yes: bool
     },
-    /// Generate Atom feeds for <repo>
-    Generate(GenerateOpts)
+    /// Copy the database to <target>, safe to use while a sync is running
+    Backup {
+        /// New file to write the backup to
+        target: PathBuf
+    },
+    /// Treat labels of different names as one, e.g. kind/bug as bug
+    Alias(AliasCommand),
+    /// Database maintenance
+    Db(DbCommand)
 }
 
+#[derive(StructOpt, Clone)]
+enum AliasCommand {
+    /// Merge issues labeled <alias> into the feed of <label>
+    Add {
+        alias: String,
+        label: String
+    },
+    Remove {
+        alias: String
+    },
+    List
+}
 
-pub type Conn = sqlx::SqliteConnection;
+#[derive(StructOpt, Clone)]
+enum DbCommand {
+    /// Delete old closed issues and repositories that are no longer wanted
+    Prune {
+        /// Delete issues closed more than this many days ago
+        #[structopt(long)]
+        closed_days: Option<u32>,
+        /// Delete this repository with all of its issues and labels
+        #[structopt(long = "repo", number_of_values = 1)]
+        repos: Vec<String>
+    },
+    /// Shrink the database file after deleting data
+    Vacuum,
+    /// Update the database schema, which commands that only read can't do
+    Migrate,
+    /// Verify the database file and find rows referring to missing data
+    Check {
+        /// Delete rows referring to missing issues, labels or repositories
+        #[structopt(long)]
+        repair: bool
+    }
+}
+
+#[derive(StructOpt, Clone)]
+enum ImportSource {
+    /// Import issues of <repo> from GH Archive event dumps (.json or .json.gz)
+    Gharchive {
+        repo: String,
+        #[structopt(required = true)]
+        files: Vec<PathBuf>
+    },
+    /// Import issues of <repo> from a JSON array or one JSON record per line, see README
+    Json {
+        repo: String,
+        /// Use - to read from stdin
+        file: PathBuf
+    },
+    /// Import issues of <repo> from CSV with a header row, see README
+    Csv {
+        repo: String,
+        /// Use - to read from stdin
+        file: PathBuf
+    },
+    /// Import issues of <repo> from the output of
+    /// `gh issue list --state all --limit <n> --json number,title,body,state,labels,author,assignees,url,updatedAt,createdAt`
+    Gh {
+        repo: String,
+        /// Use - to read from stdin
+        file: PathBuf
+    },
+    /// Import the output of `export --format ndjson`, restoring every repository it contains
+    Export {
+        /// Use - to read from stdin
+        file: PathBuf
+    },
+    /// Import the results of a JQL search from Jira, one repository per project
+    Jira {
+        /// Jira site, e.g. https://example.atlassian.net
+        base_url: Url,
+        /// Search selecting the issues to import, e.g. "project = FOO"
+        jql: String,
+        /// Account email, leave empty for anonymous access
+        #[structopt(long, env = "JIRA_USER")]
+        user: Option<String>,
+        #[structopt(long = "api-token", env = "JIRA_API_TOKEN", hide_env_values = true)]
+        api_token: Option<String>
+    }
+}
+
+impl OptMode {
+    /// Repository the command works on, which selects the database with --data-dir
+    fn repo(&self) -> Option<&str> {
+        match self {
+            OptMode::Sync(SyncOpts { repo, .. })
+            | OptMode::Remove { repo, .. }
+            | OptMode::Import(ImportSource::Gharchive { repo, .. })
+            | OptMode::Import(ImportSource::Json { repo, .. })
+            | OptMode::Import(ImportSource::Csv { repo, .. })
+            | OptMode::Import(ImportSource::Gh { repo, .. }) => Some(repo),
+            OptMode::Generate(GenerateOpts { repo, .. })
+            | OptMode::Export(ExportOpts { repo, .. })
+            | OptMode::Stats { repo } => repo.as_deref(),
+            OptMode::List
+            | OptMode::Import(ImportSource::Export { .. })
+            | OptMode::Import(ImportSource::Jira { .. })
+            | OptMode::Alias(_)
+            | OptMode::Backup { .. }
+            | OptMode::Db(_) => None
+        }
+    }
+
+    /// Whether the command stores issues of its repository, creating its database with --data-dir
+    fn stores_issues(&self) -> bool {
+        match self {
+            OptMode::Sync(_)
+            | OptMode::Import(_) => true,
+            _ => false
+        }
+    }
+
+    /// Whether the command can run on each database of --data-dir in turn
+    fn is_per_database(&self) -> bool {
+        match self {
+            OptMode::List
+            | OptMode::Stats { repo: None }
+            | OptMode::Db(DbCommand::Vacuum)
+            | OptMode::Db(DbCommand::Migrate)
+            | OptMode::Db(DbCommand::Check { .. })
+            | OptMode::Alias(_) => true,
+            OptMode::Db(DbCommand::Prune { repos, .. }) => repos.is_empty(),
+            OptMode::Generate(GenerateOpts { all, .. }) => all.is_some(),
+            _ => false
+        }
+    }
 
-async fn init_db(conn: &mut Conn) {
-    // Naive init, all data is re-fetch-able, so no support for migrations
-    sqlx::query(r#"
-        PRAGMA foreign_keys = ON;
-        PRAGMA synchronous = OFF;
-
-        CREATE TABLE IF NOT EXISTS repositories(
-            id integer PRIMARY KEY,
-            owner text, name text,
-            UNIQUE (owner, name)
-        );
-
-        CREATE TABLE IF NOT EXISTS issues(
-            repo integer REFERENCES repositories,
-            number integer,
-            state integer, title text, body text,
-            user_login text,
-            html_url text,
-            updated_at integer,
-            PRIMARY KEY (repo, number)
-        );
-        CREATE INDEX IF NOT EXISTS issues_state ON issues (repo, number, state);
-
-        CREATE TABLE IF NOT EXISTS labels(
-            id integer PRIMARY KEY,
-            repo integer REFERENCES repositories,
-            name text,
-            UNIQUE (repo, name)
-        );
-
-        CREATE TABLE IF NOT EXISTS is_labeled(
-            repo integer, issue integer,
-            label integer RFERENCES labels,
-            PRIMARY KEY (repo, issue, label),
-            FOREIGN KEY (repo, issue) REFERENCES issues
-        );
-    "#).execute(conn)
-       .await
-       .expect("Failed to init database");
+    /// Commands that never write, which open the database read-only
+    fn is_read_only(&self) -> bool {
+        match self {
+            OptMode::List
+            | OptMode::Generate(_)
+            | OptMode::Export(_)
+            | OptMode::Stats { .. }
+            | OptMode::Backup { .. }
+            | OptMode::Alias(AliasCommand::List) => true,
+            _ => false
+        }
+    }
 }
 
+pub type Conn = sqlx::SqliteConnection;
+
 pub fn parse_repo(combined: &str) -> Result<(String, String)> {
+    // GitLab owners may be nested groups, so only the last segment is the name
     let mut parts = combined
-        .split('/')
+        .rsplitn(2, '/')
         .map(str::trim)
         .map(str::to_owned);
 
     match (parts.next(), parts.next()) {
-        (Some(r), Some(n)) => Ok((r, n)),
+        (Some(n), Some(r)) if !n.is_empty() && !r.is_empty() => Ok((r, n)),
         _ => Err(anyhow!("invalid repo format, expected owner/name: '{}'", combined))
     }
 }
 
+/// Parse <name>=<value>, split at the last = as names may contain one
+fn parse_mapping(mapping: &str) -> Result<(String, String)> {
+    match mapping.rfind('=') {
+        Some(i) if i > 0 => Ok((mapping[..i].to_owned(), mapping[i + 1..].to_owned())),
+        _ => Err(anyhow!("invalid mapping '{}', expected <name>=<value>", mapping))
+    }
+}
+
+/// Parse the number of comments sync stores per issue, limited to the page size of the GitHub API
+fn parse_comments(comments: &str) -> Result<u32> {
+    match comments.parse() {
+        Ok(comments) if comments <= 100 => Ok(comments),
+        _ => Err(anyhow!("invalid number of comments '{}', expected 0 to 100", comments))
+    }
+}
+
+/// Parse an RFC 3339 timestamp, or a date meaning midnight UTC
+fn parse_time(time: &str) -> Result<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(time) {
+        return Ok(time.with_timezone(&Utc))
+    }
+    let date = NaiveDate::parse_from_str(time, "%Y-%m-%d")
+        .with_context(|| format!("invalid time '{}', expected YYYY-MM-DD or an RFC 3339 timestamp", time))?;
+    Ok(DateTime::from_utc(date.and_hms(0, 0, 0), Utc))
+}
+
 fn main() -> Result<()> {
     let env_spec = env::var("RUST_LOG")
         .unwrap_or_else(|_| String::from("info"));
@@ -126,31 +569,256 @@ fn main() -> Result<()> {
     let opt = Opt::from_args();
 
     smol::run(async {
-        let pool = SqlitePool::new("sqlite:./issues.sqlite").await?;
-        init_db(&mut *pool.acquire().await?).await;
-
-        match opt.mode {
-            OptMode::List => {
-                let repos = query::list_repositories(&mut *pool.acquire().await?).await?;
-                for query::RepositoryInfo { owner, name, label_count, issue_count, .. } in repos {
-                    println!("{}/{} ({} labels, {} issues)", owner, name, label_count, issue_count);
-                }
-                Ok(())
-            },
-            OptMode::Sync { repo, github_api_token } => {
-                info!("sync");
-                let repo = parse_repo(&repo)?;
-                let mut tx = pool.begin().await?;
-                query::labels::update(&mut tx, &github_api_token, repo.clone())
-                    .await
-                    .context("Failed to update labels")?;
-                query::issues::update(&mut tx, &github_api_token, repo)
-                    .await
-                    .context("Failed to update issues")?;
-                tx.commit().await?;
-                Ok(())
-            },
-            OptMode::Generate(opts) => generate::run(&mut *pool.acquire().await?, opts).await
+        // Every output of a config file runs as a command of its own, possibly on different databases
+        if let OptMode::Generate(GenerateOpts { config: Some(config), .. }) = &opt.mode {
+            for opts in generate::config::load(config)? {
+                dispatch(&opt.db, OptMode::Generate(opts)).await?;
+            }
+            return Ok(())
         }
+
+        dispatch(&opt.db, opt.mode).await
     })
 }
+
+/// Run `mode` on the database of --db, or on those of --data-dir it concerns
+async fn dispatch(db_opts: &db::DbOpts, mode: OptMode) -> Result<()> {
+    let data_dir = match &db_opts.data_dir {
+        Some(data_dir) => data_dir,
+        None => return run(db_opts, &db_opts.path, mode).await
+    };
+    match mode.repo() {
+        Some(repo) => {
+            let path = db::repository_path(data_dir, &parse_repo(repo)?, mode.stores_issues())?;
+            let remove = matches!(mode, OptMode::Remove { .. });
+            run(db_opts, &path, mode).await?;
+            if remove {
+                db::remove_repository_database(&path)?;
+            }
+            Ok(())
+        },
+        None if mode.is_per_database() => {
+            for path in db::find_databases(data_dir)? {
+                info!("database {}", path.display());
+                run(db_opts, &path, mode.clone()).await?;
+            }
+            Ok(())
+        },
+        None => Err(anyhow!("This command needs a single database, use --db instead of --data-dir"))
+    }
+}
+
+async fn run(db_opts: &db::DbOpts, path: &Path, mode: OptMode) -> Result<()> {
+    let read_only = mode.is_read_only();
+    let database = db::Database::open(db_opts, path, read_only).await?;
+    let compress_bodies = !db_opts.uncompressed_bodies;
+    if read_only {
+        // A backup copies the database as it is, so it can also be taken before migrating
+        if !matches!(mode, OptMode::Backup { .. }) {
+            db::check_version(&mut *database.acquire().await?).await?;
+        }
+    } else {
+        db::migrate(&mut *database.acquire().await?, compress_bodies).await?;
+    }
+
+    match mode {
+        OptMode::List => {
+            let repos = query::list_repositories(&mut *database.acquire().await?).await?;
+            for query::RepositoryInfo { owner, name, label_count, issue_count, .. } in repos {
+                println!("{}/{} ({} labels, {} issues)", owner, name, label_count, issue_count);
+            }
+            Ok(())
+        },
+        OptMode::Sync(opts) => {
+            info!("sync");
+            let mut repo = parse_repo(&opts.repo)?;
+            let base_url = opts.base_url.clone()
+                .unwrap_or_else(|| opts.backend.default_base_url());
+            if opts.base_url.is_some() && !opts.backend.is_self_hostable() {
+                return Err(anyhow!("--base-url is not supported for backend {}", opts.backend));
+            }
+            if !opts.labels.is_empty() && opts.backend != Backend::Github {
+                return Err(anyhow!("--label is not supported for backend {}", opts.backend));
+            }
+            // Taken before fetching, so updates made during the sync are fetched again next time
+            let started_at = Utc::now().timestamp();
+            let mut tx = database.begin().await?;
+            // Stored first, as the backend determines the profile URLs of authors
+            let mut repo_id = query::repo_id(&mut tx, &repo.0, &repo.1).await?;
+            query::set_backend(&mut tx, repo_id, opts.backend, &base_url).await?;
+            match opts.backend {
+                Backend::Github => {
+                    let github_api_token = opts.github_api_token
+                        .ok_or_else(|| anyhow!("GitHub API token required, set --github-api-token or GITHUB_TOKEN"))?;
+                    repo = query::labels::update(&mut tx, &github_api_token, repo)
+                        .await
+                        .context("Failed to update labels")?;
+                    // A rename may have merged the repository into the one already stored under its new name
+                    repo_id = query::repo_id(&mut tx, &repo.0, &repo.1).await?;
+                    query::set_backend(&mut tx, repo_id, opts.backend, &base_url).await?;
+                    query::issues::update(&mut tx, &github_api_token, repo.clone(), &opts.labels, opts.comments, compress_bodies)
+                        .await
+                        .context("Failed to update issues")?;
+                },
+                Backend::Gitlab => {
+                    query::gitlab::update(&mut tx, &base_url, opts.gitlab_api_token.as_ref(), repo.clone(), compress_bodies)
+                        .await
+                        .context("Failed to update GitLab project")?;
+                },
+                Backend::Gitea => {
+                    query::gitea::update(&mut tx, &base_url, opts.gitea_api_token.as_ref(), repo.clone(), compress_bodies)
+                        .await
+                        .context("Failed to update Gitea repository")?;
+                },
+                Backend::Bitbucket => {
+                    query::bitbucket::update(&mut tx, opts.bitbucket_api_token.as_ref(), repo.clone(), compress_bodies)
+                        .await
+                        .context("Failed to update Bitbucket repository")?;
+                },
+                Backend::Sourcehut => {
+                    let sourcehut_api_token = opts.sourcehut_api_token
+                        .ok_or_else(|| anyhow!("sourcehut API token required, set --sourcehut-api-token or SRHT_TOKEN"))?;
+                    query::sourcehut::update(&mut tx, &base_url, &sourcehut_api_token, repo.clone(), compress_bodies)
+                        .await
+                        .context("Failed to update sourcehut tracker")?;
+                },
+                Backend::Jira => {
+                    return Err(anyhow!("Jira projects can't be synchronised, use `import jira` instead"));
+                }
+            }
+            query::set_last_synced(&mut tx, repo_id, &opts.labels, started_at).await?;
+            tx.commit().await?;
+
+            match opts.then {
+                Some(AfterSync::Generate(opts)) => generate::run(&database, opts).await,
+                None if db::is_in_memory(path) => {
+                    warn!("nothing is kept of the in-memory database, use `sync <repo> generate …` to generate feeds");
+                    Ok(())
+                },
+                None => Ok(())
+            }
+        },
+        OptMode::Generate(opts) => generate::run(&database, opts).await,
+        OptMode::Export(opts) => export::run(&mut *database.acquire().await?, opts).await,
+        OptMode::Import(ImportSource::Gharchive { repo, files }) => {
+            let repo = parse_repo(&repo)?;
+            let mut tx = database.begin().await?;
+            import::gharchive::import(&mut tx, repo, &files, compress_bodies)
+                .await
+                .context("Failed to import GH Archive events")?;
+            tx.commit().await?;
+            Ok(())
+        },
+        OptMode::Import(ImportSource::Json { repo, file }) => {
+            let repo = parse_repo(&repo)?;
+            let mut tx = database.begin().await?;
+            import::records::import_json(&mut tx, repo, &file, compress_bodies)
+                .await
+                .context("Failed to import JSON records")?;
+            tx.commit().await?;
+            Ok(())
+        },
+        OptMode::Import(ImportSource::Csv { repo, file }) => {
+            let repo = parse_repo(&repo)?;
+            let mut tx = database.begin().await?;
+            import::records::import_csv(&mut tx, repo, &file, compress_bodies)
+                .await
+                .context("Failed to import CSV records")?;
+            tx.commit().await?;
+            Ok(())
+        },
+        OptMode::Import(ImportSource::Gh { repo, file }) => {
+            let repo = parse_repo(&repo)?;
+            let mut tx = database.begin().await?;
+            import::gh::import(&mut tx, repo, &file, compress_bodies)
+                .await
+                .context("Failed to import gh output")?;
+            tx.commit().await?;
+            Ok(())
+        },
+        OptMode::Import(ImportSource::Export { file }) => {
+            let mut tx = database.begin().await?;
+            import::records::import_export(&mut tx, &file, compress_bodies)
+                .await
+                .context("Failed to import export")?;
+            tx.commit().await?;
+            Ok(())
+        },
+        OptMode::Stats { repo } => stats::run(&mut *database.acquire().await?, repo).await,
+        OptMode::Remove { repo, yes } => {
+            let (owner, name) = parse_repo(&repo)?;
+            let mut tx = database.begin().await?;
+            let info = query::list_repositories(&mut tx).await?
+                .into_iter()
+                .find(|info| info.owner == owner && info.name == name)
+                .ok_or_else(|| anyhow!("Unknown repository {}", repo))?;
+            if !yes {
+                println!("{} ({} labels, {} issues) would be removed", repo, info.label_count, info.issue_count);
+                return Err(anyhow!("Not removing {} without --yes", repo));
+            }
+            let repo_id = query::repo_id(&mut tx, &owner, &name).await?;
+            query::remove_repository(&mut tx, repo_id).await?;
+            tx.commit().await?;
+            info!("removed {} ({} labels, {} issues)", repo, info.label_count, info.issue_count);
+            Ok(())
+        },
+        OptMode::Backup { target } => {
+            db::backup(&mut *database.acquire().await?, &target).await?;
+            info!("backed up {} to {}", path.display(), target.display());
+            Ok(())
+        },
+        OptMode::Alias(AliasCommand::Add { alias, label }) =>
+            query::add_label_alias(&mut *database.acquire().await?, &alias, &label).await,
+        OptMode::Alias(AliasCommand::Remove { alias }) => {
+            if !query::remove_label_alias(&mut *database.acquire().await?, &alias).await? {
+                return Err(anyhow!("Unknown alias {}", alias));
+            }
+            Ok(())
+        },
+        OptMode::Alias(AliasCommand::List) => {
+            for (alias, label) in query::label_aliases(&mut *database.acquire().await?).await? {
+                println!("{} -> {}", alias, label);
+            }
+            Ok(())
+        },
+        OptMode::Db(DbCommand::Prune { closed_days, repos }) => {
+            let mut tx = database.begin().await?;
+            for repo in repos {
+                let (owner, name) = parse_repo(&repo)?;
+                let repo_id = query::find_repo_id(&mut tx, &owner, &name).await?
+                    .ok_or_else(|| anyhow!("Unknown repository {}", repo))?;
+                query::remove_repository(&mut tx, repo_id).await?;
+                info!("removed {}", repo);
+            }
+            if let Some(days) = closed_days {
+                let before = Utc::now() - chrono::Duration::days(days.into());
+                let pruned = db::prune_closed(&mut tx, before.timestamp()).await?;
+                info!("removed {} issues closed before {}", pruned, before.to_rfc3339());
+            }
+            tx.commit().await?;
+            Ok(())
+        },
+        OptMode::Db(DbCommand::Vacuum) => db::vacuum(&mut *database.acquire().await?).await,
+        OptMode::Db(DbCommand::Migrate) => Ok(()),
+        OptMode::Db(DbCommand::Check { repair }) => {
+            let mut tx = database.begin().await?;
+            // Keep repairs even if problems remain
+            let checked = db::check(&mut tx, repair).await;
+            tx.commit().await?;
+            checked
+        },
+        OptMode::Import(ImportSource::Jira { base_url, jql, user, api_token }) => {
+            let auth = match (user, api_token) {
+                (Some(user), Some(token)) => query::rest::Auth::Basic(user, token),
+                (None, None) => query::rest::Auth::None,
+                _ => return Err(anyhow!("Jira authentication needs both --user and --api-token"))
+            };
+            let mut tx = database.begin().await?;
+            import::jira::import(&mut tx, &base_url, &auth, &jql, compress_bodies)
+                .await
+                .context("Failed to import Jira issues")?;
+            tx.commit().await?;
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use reqwest::Client;
+use serde::Deserialize;
+use url::Url;
+
+use chrono::{ Utc, TimeZone };
+use tracing::{ info, debug };
+
+use crate::{ Conn, query::{ *, issues::IssueState } };
+
+// https://developer.atlassian.com/cloud/bitbucket/rest/api-group-issue-tracker/
+
+static API_ENDPOINT: &str = "https://api.bitbucket.org/2.0/repositories";
+
+#[derive(Deserialize)]
+struct Page {
+    values: Vec<Issue>,
+    next: Option<String>
+}
+
+#[derive(Deserialize)]
+struct Issue {
+    id: i64,
+    title: String,
+    content: Option<Content>,
+    state: String,
+    kind: Option<String>,
+    component: Option<Named>,
+    reporter: Option<User>,
+    assignee: Option<User>,
+    links: Links,
+    updated_on: String,
+    created_on: String,
+    votes: Option<i64>
+}
+
+#[derive(Deserialize)]
+struct Content {
+    raw: Option<String>
+}
+
+#[derive(Deserialize)]
+struct Named {
+    name: String
+}
+
+#[derive(Deserialize)]
+struct User {
+    nickname: Option<String>,
+    links: Option<UserLinks>
+}
+
+#[derive(Deserialize)]
+struct UserLinks {
+    avatar: Option<Link>
+}
+
+#[derive(Deserialize)]
+struct Links {
+    html: Link
+}
+
+#[derive(Deserialize)]
+struct Link {
+    href: String
+}
+
+pub async fn update(mut conn: &mut Conn, api_token: Option<&String>, (ref owner, ref name): (String, String), compress_bodies: bool) -> anyhow::Result<()> {
+    let repo = repo_id(&mut conn, owner, name).await?;
+    let client = Client::new();
+    let auth = rest::Auth::bearer(api_token);
+
+    let last_updated = last_updated(conn, repo)
+        .await?
+        .map(|t| Utc.timestamp(t, 0).to_rfc3339());
+    info!("updating repo {}/{} ({}), last update from {:?}", owner, name, repo, last_updated);
+
+    let mut next = Some({
+        let mut url = Url::parse(API_ENDPOINT)?;
+        url.path_segments_mut()
+            .expect("API endpoint cannot be a base")
+            .push(owner).push(name).push("issues");
+        url.query_pairs_mut()
+            .append_pair("pagelen", "50")
+            .append_pair("sort", "updated_on");
+        if let Some(since) = &last_updated {
+            url.query_pairs_mut().append_pair("q", &format!("updated_on >= {}", since));
+        }
+        url
+    });
+
+    while let Some(url) = next {
+        let page: Page = rest::get(&client, &auth, &url).await?
+            .json().await?;
+        next = page.next
+            .map(|next| Url::parse(&next))
+            .transpose()?;
+        debug!("has_next_page: {}", next.is_some());
+
+        for issue in page.values {
+            debug!("#{}: {}", issue.id, issue.title);
+            let state = match &issue.state[..] {
+                "new" | "open" | "submitted" | "on hold" => IssueState::OPEN,
+                "resolved" | "invalid" | "duplicate" | "wontfix" | "closed" => IssueState::CLOSED,
+                other => IssueState::Other(other.to_owned())
+            };
+            let ts = chrono::DateTime::parse_from_rfc3339(&issue.updated_on)?
+                .timestamp();
+
+            // Bitbucket issues have no labels, kind and component are the closest match
+            let labels = issue.kind.into_iter()
+                .chain(issue.component.map(|c| c.name))
+                .collect();
+
+            store_issue(&mut conn, repo, &IssueRecord {
+                number: issue.id,
+                state: state.to_integer(),
+                title: issue.title,
+                body: issue.content
+                    .and_then(|c| c.raw)
+                    .unwrap_or_default(),
+                body_markdown: true,
+                user_avatar_url: issue.reporter.as_ref()
+                    .and_then(|r| r.links.as_ref())
+                    .and_then(|links| links.avatar.as_ref())
+                    .map(|avatar| avatar.href.clone()),
+                user_login: issue.reporter
+                    .and_then(|r| r.nickname)
+                    .unwrap_or_else(|| String::from("ghost")),
+                html_url: issue.links.html.href,
+                updated_at: ts,
+                created_at: Some(chrono::DateTime::parse_from_rfc3339(&issue.created_on)?.timestamp()),
+                reactions: issue.votes,
+                comment_count: None,
+                labels,
+                assignees: issue.assignee
+                    .and_then(|a| a.nickname)
+                    .into_iter()
+                    .collect(),
+                comments: None,
+                closed_at: None,
+                milestone: None
+            }, compress_bodies).await?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use reqwest::Client;
+use serde::Deserialize;
+use url::Url;
+
+use chrono::{ Utc, TimeZone };
+use tracing::{ info, debug };
+
+use crate::{ Conn, query::{ *, issues::IssueState } };
+
+// https://try.gitea.io/api/swagger, shared by Forgejo
+
+// Maximum page size of the default Gitea configuration
+static PAGE_SIZE: usize = 50;
+
+#[derive(Deserialize)]
+struct Label {
+    name: String,
+    color: Option<String>
+}
+
+#[derive(Deserialize)]
+struct Issue {
+    number: i64,
+    title: String,
+    body: Option<String>,
+    state: String,
+    user: Option<User>,
+    assignees: Option<Vec<User>>,
+    html_url: String,
+    updated_at: String,
+    created_at: String,
+    comments: Option<i64>,
+    #[serde(default)]
+    labels: Vec<Label>
+}
+
+#[derive(Deserialize)]
+struct User {
+    login: String,
+    avatar_url: Option<String>
+}
+
+fn repo_url(base_url: &Url, owner: &str, name: &str, resource: &str) -> Url {
+    let mut url = base_url.clone();
+    url.path_segments_mut()
+        .expect("base url cannot be a base")
+        .pop_if_empty()
+        .extend(&["api", "v1", "repos", owner, name, resource]);
+    url
+}
+
+/// Fetch pages of `url` until X-Total-Count items arrived, or until an empty page without that header.
+/// Pages can come back shorter than requested, as instances cap them at their MAX_RESPONSE_ITEMS
+async fn fetch_pages<T: serde::de::DeserializeOwned>(client: &Client, auth: &rest::Auth, url: Url) -> anyhow::Result<Vec<T>> {
+    let mut items = Vec::new();
+
+    for page in 1.. {
+        let mut url = url.clone();
+        url.query_pairs_mut()
+            .append_pair("limit", &PAGE_SIZE.to_string())
+            .append_pair("page", &page.to_string());
+
+        let res = rest::get(client, auth, &url).await?;
+        let total_count = res.headers().get("x-total-count")
+            .and_then(|count| count.to_str().ok()?.parse::<usize>().ok());
+        let page_items = res.json::<Vec<T>>().await?;
+        let has_next_page = !page_items.is_empty()
+            && total_count.map_or(true, |total| items.len() + page_items.len() < total);
+        debug!("has_next_page: {}", has_next_page);
+
+        items.extend(page_items);
+        if !has_next_page { break }
+    }
+
+    Ok(items)
+}
+
+pub async fn update(mut conn: &mut Conn, base_url: &Url, api_token: Option<&String>, (ref owner, ref name): (String, String), compress_bodies: bool) -> anyhow::Result<()> {
+    let repo = repo_id(&mut conn, owner, name).await?;
+    let client = Client::new();
+    let auth = rest::Auth::bearer(api_token);
+
+    let labels: Vec<Label> = fetch_pages(&client, &auth, repo_url(base_url, owner, name, "labels")).await?;
+    for label in labels {
+        debug!("{}: {}", repo, label.name);
+        store_label(&mut conn, repo, &label.name, label.color.as_deref()).await?;
+    }
+
+    let last_updated = last_updated(conn, repo)
+        .await?
+        .map(|t| Utc.timestamp(t, 0).to_rfc3339());
+    info!("updating repo {}/{} ({}), last update from {:?}", owner, name, repo, last_updated);
+
+    let mut issues_url = repo_url(base_url, owner, name, "issues");
+    issues_url.query_pairs_mut()
+        .append_pair("state", "all")
+        .append_pair("type", "issues");
+    if let Some(since) = &last_updated {
+        issues_url.query_pairs_mut().append_pair("since", since);
+    }
+
+    let issues: Vec<Issue> = fetch_pages(&client, &auth, issues_url).await?;
+    for issue in issues {
+        debug!("#{}: {}", issue.number, issue.title);
+        let state = IssueState::from_name(&issue.state);
+        let ts = chrono::DateTime::parse_from_rfc3339(&issue.updated_at)?
+            .timestamp();
+
+        store_issue(&mut conn, repo, &IssueRecord {
+            number: issue.number,
+            state: state.to_integer(),
+            title: issue.title,
+            body: issue.body.unwrap_or_default(),
+            body_markdown: true,
+            user_avatar_url: issue.user.as_ref().and_then(|user| user.avatar_url.clone()),
+            user_login: issue.user
+                .map(|user| user.login)
+                .unwrap_or_else(|| String::from("ghost")),
+            html_url: issue.html_url,
+            updated_at: ts,
+            created_at: Some(chrono::DateTime::parse_from_rfc3339(&issue.created_at)?.timestamp()),
+            reactions: None,
+            comment_count: issue.comments,
+            labels: issue.labels.into_iter().map(|l| l.name).collect(),
+            assignees: issue.assignees.unwrap_or_default()
+                .into_iter()
+                .map(|user| user.login)
+                .collect(),
+            comments: None,
+            closed_at: None,
+            milestone: None
+        }, compress_bodies).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use reqwest::Client;
+use serde::Deserialize;
+use url::Url;
+
+use chrono::{ Utc, TimeZone };
+use tracing::{ info, debug };
+
+use crate::{ Conn, query::{ *, issues::IssueState } };
+
+// https://docs.gitlab.com/ee/api/README.html
+
+static PER_PAGE: &str = "100";
+
+#[derive(Deserialize)]
+struct Label {
+    name: String,
+    color: Option<String>
+}
+
+#[derive(Deserialize)]
+struct Issue {
+    iid: i64,
+    title: String,
+    description: Option<String>,
+    state: String,
+    author: Option<User>,
+    #[serde(default)]
+    assignees: Vec<User>,
+    web_url: String,
+    updated_at: String,
+    created_at: String,
+    upvotes: Option<i64>,
+    user_notes_count: Option<i64>,
+    #[serde(default)]
+    labels: Vec<String>
+}
+
+#[derive(Deserialize)]
+struct User {
+    username: String,
+    avatar_url: Option<String>
+}
+
+fn project_url(base_url: &Url, owner: &str, name: &str, resource: &str) -> Url {
+    let mut url = base_url.clone();
+    url.path_segments_mut()
+        .expect("base url cannot be a base")
+        .pop_if_empty()
+        .extend(&["api", "v4", "projects", &format!("{}/{}", owner, name), resource]);
+    url
+}
+
+/// Fetch all pages of `url`, following GitLab's X-Next-Page header
+async fn fetch_pages<T: serde::de::DeserializeOwned>(client: &Client, auth: &rest::Auth, url: Url) -> anyhow::Result<Vec<T>> {
+    let mut items = Vec::new();
+    let mut page = Some(String::from("1"));
+
+    while let Some(current) = page {
+        let mut url = url.clone();
+        url.query_pairs_mut()
+            .append_pair("per_page", PER_PAGE)
+            .append_pair("page", &current);
+
+        let res = rest::get(client, auth, &url).await?;
+        page = res.headers()
+            .get("x-next-page")
+            .and_then(|next| next.to_str().ok())
+            .filter(|next| !next.is_empty())
+            .map(str::to_owned);
+        debug!("next page: {:?}", page);
+
+        items.extend(res.json::<Vec<T>>().await?);
+    }
+
+    Ok(items)
+}
+
+pub async fn update(mut conn: &mut Conn, base_url: &Url, api_token: Option<&String>, (ref owner, ref name): (String, String), compress_bodies: bool) -> anyhow::Result<()> {
+    let repo = repo_id(&mut conn, owner, name).await?;
+    let client = Client::new();
+    let auth = rest::Auth::bearer(api_token);
+
+    let labels: Vec<Label> = fetch_pages(&client, &auth, project_url(base_url, owner, name, "labels")).await?;
+    for label in labels {
+        debug!("{}: {}", repo, label.name);
+        store_label(&mut conn, repo, &label.name, label.color.as_deref()).await?;
+    }
+
+    let last_updated = last_updated(conn, repo)
+        .await?
+        .map(|t| Utc.timestamp(t, 0).to_rfc3339());
+    info!("updating project {}/{} ({}), last update from {:?}", owner, name, repo, last_updated);
+
+    let mut issues_url = project_url(base_url, owner, name, "issues");
+    issues_url.query_pairs_mut()
+        .append_pair("scope", "all")
+        .append_pair("order_by", "updated_at")
+        .append_pair("sort", "asc");
+    if let Some(since) = &last_updated {
+        issues_url.query_pairs_mut().append_pair("updated_after", since);
+    }
+
+    let issues: Vec<Issue> = fetch_pages(&client, &auth, issues_url).await?;
+    for issue in issues {
+        debug!("#{}: {}", issue.iid, issue.title);
+        let state = match &issue.state[..] {
+            "opened" => IssueState::OPEN,
+            "closed" => IssueState::CLOSED,
+            other => IssueState::Other(other.to_owned())
+        };
+        let ts = chrono::DateTime::parse_from_rfc3339(&issue.updated_at)?
+            .timestamp();
+
+        store_issue(&mut conn, repo, &IssueRecord {
+            number: issue.iid,
+            state: state.to_integer(),
+            title: issue.title,
+            body: issue.description.unwrap_or_default(),
+            body_markdown: true,
+            user_avatar_url: issue.author.as_ref().and_then(|author| author.avatar_url.clone()),
+            user_login: issue.author
+                .map(|author| author.username)
+                .unwrap_or_else(|| String::from("ghost")),
+            html_url: issue.web_url,
+            updated_at: ts,
+            created_at: Some(chrono::DateTime::parse_from_rfc3339(&issue.created_at)?.timestamp()),
+            reactions: issue.upvotes,
+            comment_count: issue.user_notes_count,
+            labels: issue.labels,
+            assignees: issue.assignees.into_iter().map(|user| user.username).collect(),
+            comments: None,
+            closed_at: None,
+            milestone: None
+        }, compress_bodies).await?;
+    }
+
+    Ok(())
+}
@@ -46,13 +46,25 @@ impl IssueState {
             Self::Other(_) => None
         }.map(str::to_owned)
     }
+
+    /// Inverse of `to_string`, as used by most REST APIs
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "open" => Self::OPEN,
+            "closed" => Self::CLOSED,
+            other => Self::Other(other.to_owned())
+        }
+    }
 }
 
 
-pub async fn update(mut conn: &mut Conn, github_api_token: &str, (ref owner, ref name): (String, String)) -> anyhow::Result<()> {
+/// Synchronise issues updated since the last sync. If `labels` isn't empty,
+/// only issues carrying at least one of them are fetched. With `comments`,
+/// the most recent comments of every updated issue are stored as well.
+pub async fn update(mut conn: &mut Conn, github_api_token: &str, (ref owner, ref name): (String, String), labels: &[String], comments: u32, compress_bodies: bool) -> anyhow::Result<()> {
     let repo = repo_id(conn, owner, name).await?;
 
-    let last_updated = last_updated(conn, repo)
+    let last_updated = last_updated_with_labels(conn, repo, labels)
         .await?
         .map(|t| Utc.timestamp(t, 0).to_rfc3339());
     info!("updating repo {}/{} ({}), last update from {:?}", owner, name, repo, last_updated);
@@ -67,7 +79,11 @@ pub async fn update(mut conn: &mut Conn, github_api_token: &str, (ref owner, ref
             owner: owner.to_owned(),
             name: name.to_owned(),
             since: last_updated.clone(),
-            after: last_cursor.clone()
+            labels: if labels.is_empty() { None } else { Some(labels.to_vec()) },
+            after: last_cursor.clone(),
+            // Without comments, only their count is requested, which needs no page size
+            comments: if comments == 0 { None } else { Some(i64::from(comments)) },
+            with_comments: comments > 0
         });
 
         let res = graphql::query(&client, github_api_token, query).await?;
@@ -93,24 +109,13 @@ pub async fn update(mut conn: &mut Conn, github_api_token: &str, (ref owner, ref
                 let ts = chrono::DateTime::parse_from_rfc3339(&issue.updated_at)
                     .expect("failed to parse datetime")
                     .timestamp();
-                let author = issue.author
-                    .map(|author| author.login)
-                    .unwrap_or_else(|| String::from("ghost"));
-
-                sqlx::query(
-                    "REPLACE INTO issues (repo, number, state, title, body, user_login, html_url, updated_at)
-                     VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
-                ).bind(repo).bind(issue.number)
-                 .bind(issue.state.to_integer()).bind(issue.title).bind(issue.body_html)
-                 .bind(author).bind(issue.url).bind(ts)
-                 .execute(&mut conn)
-                 .await?;
-
-                sqlx::query(
-                    "DELETE FROM is_labeled WHERE repo=? AND issue=?"
-                ).bind(repo).bind(issue.number)
-                 .execute(&mut conn)
-                 .await?;
+                let created_at = chrono::DateTime::parse_from_rfc3339(&issue.created_at)
+                    .expect("failed to parse datetime")
+                    .timestamp();
+                let (author, avatar_url) = match issue.author {
+                    Some(author) => (author.login, Some(author.avatar_url)),
+                    None => (String::from("ghost"), None)
+                };
 
                 let labels = issue.labels
                     .map(|l| l.edges)
@@ -119,16 +124,68 @@ pub async fn update(mut conn: &mut Conn, github_api_token: &str, (ref owner, ref
                     .into_iter()
                     .flatten()
                     .map(|l| l.node)
-                    .flatten();
-
-                for label in labels {
-                    debug!("label: {}", label.name);
-                    sqlx::query(
-                        "INSERT INTO is_labeled (repo, issue, label) VALUES (?, ?, (SELECT id FROM labels WHERE name=?))"
-                    ).bind(repo).bind(issue.number).bind(label.name)
-                     .execute(&mut conn)
-                     .await?;
-                }
+                    .flatten()
+                    .map(|l| l.name)
+                    .collect::<Vec<_>>();
+                debug!("labels: {:?}", labels);
+
+                let assignees = issue.assignees.nodes
+                    .unwrap_or_default()
+                    .into_iter()
+                    .flatten()
+                    .map(|user| user.login)
+                    .collect();
+
+                let comments = if comments == 0 { None } else {
+                    Some(issue.comments.nodes
+                        .unwrap_or_default()
+                        .into_iter()
+                        .flatten()
+                        .map(|comment| CommentRecord {
+                            author: comment.author
+                                .map(|author| author.login)
+                                .unwrap_or_else(|| String::from("ghost")),
+                            created_at: chrono::DateTime::parse_from_rfc3339(&comment.created_at)
+                                .expect("failed to parse datetime")
+                                .timestamp(),
+                            body: comment.body_html,
+                            body_markdown: false
+                        })
+                        .collect())
+                };
+
+                let milestone = issue.milestone.map(|milestone| MilestoneRecord {
+                    number: milestone.number,
+                    title: milestone.title,
+                    html_url: milestone.url,
+                    due_on: milestone.due_on.map(|due_on| chrono::DateTime::parse_from_rfc3339(&due_on)
+                        .expect("failed to parse datetime")
+                        .timestamp()),
+                    closed: milestone.closed
+                });
+                let closed_at = issue.closed_at.map(|closed_at| chrono::DateTime::parse_from_rfc3339(&closed_at)
+                    .expect("failed to parse datetime")
+                    .timestamp());
+
+                store_issue(&mut conn, repo, &IssueRecord {
+                    number: issue.number,
+                    state: issue.state.to_integer(),
+                    title: issue.title,
+                    body: issue.body_html,
+                    body_markdown: false,
+                    user_login: author,
+                    user_avatar_url: avatar_url,
+                    html_url: issue.url,
+                    updated_at: ts,
+                    created_at: Some(created_at),
+                    reactions: Some(issue.reactions.total_count),
+                    comment_count: Some(issue.comments.total_count),
+                    labels,
+                    assignees,
+                    comments,
+                    closed_at,
+                    milestone
+                }, compress_bodies).await?;
             }
         }
     }
@@ -1,7 +1,7 @@
 use graphql_client::{ GraphQLQuery, Response };
 use reqwest::Client;
 
-use tracing::{ error, debug };
+use tracing::{ error, warn, debug };
 
 use crate::{ Conn, query::* };
 
@@ -16,8 +16,11 @@ type URI = String;
 )]
 pub struct RepoLabels;
 
-pub async fn update(mut conn: &mut Conn, github_api_token: &str, (ref owner, ref name): (String, String)) -> anyhow::Result<()> {
-    let repo = repo_id(&mut conn, owner, name).await?;
+/// Returns the current owner/name of the repository, which differs from the
+/// requested one if the repository was renamed or transferred.
+pub async fn update(mut conn: &mut Conn, github_api_token: &str, (owner, name): (String, String)) -> anyhow::Result<(String, String)> {
+    let (mut owner, mut name) = (owner, name);
+    let mut repo = repo_id(&mut conn, &owner, &name).await?;
 
     let client = Client::new();
 
@@ -25,8 +28,8 @@ pub async fn update(mut conn: &mut Conn, github_api_token: &str, (ref owner, ref
     let mut last_cursor = None;
     while has_next_page {
         let query = RepoLabels::build_query(repo_labels::Variables {
-            owner: owner.to_owned(),
-            name: name.to_owned(),
+            owner: owner.clone(),
+            name: name.clone(),
             after: last_cursor.clone()
         });
 
@@ -41,7 +44,15 @@ pub async fn update(mut conn: &mut Conn, github_api_token: &str, (ref owner, ref
             .expect("Missing response data")
             .repository
             .expect("Missing repository");
-    
+
+        let (current_owner, current_name) = crate::parse_repo(&repository.name_with_owner)?;
+        if (&current_owner, &current_name) != (&owner, &name) {
+            warn!("{}/{} was renamed to {}/{}", owner, name, current_owner, current_name);
+            repo = rename_repository(&mut conn, repo, (&owner, &name), (&current_owner, &current_name)).await?;
+            owner = current_owner;
+            name = current_name;
+        }
+
         if repository.labels.is_none() { break }
         let labels = repository.labels.unwrap();
         has_next_page = labels.page_info.has_next_page;
@@ -52,14 +63,10 @@ pub async fn update(mut conn: &mut Conn, github_api_token: &str, (ref owner, ref
             last_cursor = Some(label.cursor);
             if let Some(label) = label.node {
                 debug!("{}: {}", repo, label.name);
-                sqlx::query(
-                    "INSERT OR IGNORE INTO labels (repo, name) VALUES (?, ?)"
-                ).bind(repo).bind(label.name)
-                 .execute(&mut conn)
-                 .await?;
+                store_label(&mut conn, repo, &label.name, Some(&label.color)).await?;
             }
         }
     }
 
-    Ok(())
+    Ok((owner, name))
 }
@@ -1,10 +1,78 @@
 use sqlx::prelude::*;
-use anyhow::{ Result, Context };
+use anyhow::{ anyhow, Result, Context };
 
-use crate::Conn;
+use std::time::Duration;
+use futures_retry::{ ErrorHandler, RetryPolicy };
+use url::Url;
+
+use crate::{ Conn, db, search, backend::Backend };
 
 pub mod issues;
 pub mod labels;
+pub mod gitlab;
+pub mod gitea;
+pub mod bitbucket;
+pub mod sourcehut;
+
+static USER_AGENT: &str = "github.com/tilpner/github-label-feed";
+
+static RETRY_DELAY: &[u64] = &[ 5, 50, 250, 1000, 5000, 25000 ];
+
+pub struct RetryStrategy;
+impl ErrorHandler<reqwest::Error> for RetryStrategy {
+    type OutError = reqwest::Error;
+
+    fn handle(&mut self, attempt: usize, e: reqwest::Error) -> RetryPolicy<Self::OutError> {
+        match RETRY_DELAY.get(attempt) {
+            Some(&ms) => RetryPolicy::WaitRetry(Duration::from_millis(ms)),
+            None => RetryPolicy::ForwardError(e)
+        }
+    }
+}
+
+/// Forge-independent view of an issue, as written to the issues table
+pub struct IssueRecord {
+    pub number: i64,
+    pub state: i64,
+    pub title: String,
+    pub body: String,
+    /// The body is Markdown rather than HTML
+    pub body_markdown: bool,
+    pub user_login: String,
+    pub user_avatar_url: Option<String>,
+    pub html_url: String,
+    pub updated_at: i64,
+    pub created_at: Option<i64>,
+    /// Reactions, votes or upvotes, whichever the forge counts
+    pub reactions: Option<i64>,
+    /// Number of comments, of which `comments` are the most recent
+    pub comment_count: Option<i64>,
+    pub labels: Vec<String>,
+    /// Logins of the users the issue is assigned to
+    pub assignees: Vec<String>,
+    /// Most recent comments, replacing the stored ones. None where they weren't fetched
+    pub comments: Option<Vec<CommentRecord>>,
+    /// Time the issue was closed, None if it's open or the forge doesn't report it
+    pub closed_at: Option<i64>,
+    pub milestone: Option<MilestoneRecord>
+}
+
+pub struct CommentRecord {
+    pub author: String,
+    pub created_at: i64,
+    pub body: String,
+    /// The body is Markdown rather than HTML
+    pub body_markdown: bool
+}
+
+pub struct MilestoneRecord {
+    /// Number of the milestone within its repository
+    pub number: i64,
+    pub title: String,
+    pub html_url: String,
+    pub due_on: Option<i64>,
+    pub closed: bool
+}
 
 #[derive(sqlx::FromRow, sqlx::Type)]
 pub struct RepositoryInfo {
@@ -26,16 +94,366 @@ pub async fn repo_id(conn: &mut Conn, owner: &str, name: &str) -> Result<i64> {
      .with_context(|| format!("Couldn't find repo '{}/{}' in database", owner, name))
 }
 
+/// Like `repo_id`, but without creating unknown repositories
+pub async fn find_repo_id(conn: &mut Conn, owner: &str, name: &str) -> Result<Option<i64>> {
+    Ok(sqlx::query_as::<_, (i64,)>(
+        "SELECT id FROM repositories WHERE owner = ? AND name = ?"
+    ).bind(owner).bind(name)
+     .fetch_optional(conn)
+     .await?
+     .map(|(id,)| id))
+}
+
+/// Issues updated this many seconds before the last sync are fetched again,
+/// covering clock skew and updates that were still in flight
+static SYNC_OVERLAP: i64 = 5 * 60;
+
+/// Lower bound of the updates to fetch, None if the repository was never synchronised.
+/// Issue timestamps aren't used, as imports may be newer than the last sync
+/// and label changes don't always touch `updated_at`
 async fn last_updated(conn: &mut Conn, repo: i64) -> Result<Option<i64>> {
-    sqlx::query_as::<_, (i64,)>(
-        "SELECT MAX(updated_at) FROM issues WHERE repo = ?",
+    sqlx::query_as::<_, (Option<i64>,)>(
+        "SELECT last_synced_at FROM repositories WHERE id = ?",
     ).bind(repo)
      .fetch_optional(conn)
      .await
-     .map(|opt| opt.map(|row| row.0))
+     .map(|opt| opt.and_then(|row| row.0).map(|t| t - SYNC_OVERLAP))
      .with_context(|| format!("Couldn't find time of last update for repo id {}", repo))
 }
 
+/// Key of a set of labels in label_syncs, independent of their order
+fn label_set(labels: &[String]) -> String {
+    let mut labels = labels.to_vec();
+    labels.sort();
+    labels.dedup();
+    labels.join("\n")
+}
+
+/// Like `last_updated`, for a sync restricted to issues carrying one of `labels`.
+/// Both the last sync of these labels and the last unrestricted sync covered them
+async fn last_updated_with_labels(conn: &mut Conn, repo: i64, labels: &[String]) -> Result<Option<i64>> {
+    let last_updated = last_updated(&mut *conn, repo).await?;
+    if labels.is_empty() {
+        return Ok(last_updated)
+    }
+    let last_label_sync = sqlx::query_as::<_, (i64,)>(
+        "SELECT last_synced_at FROM label_syncs WHERE repo = ? AND labels = ?"
+    ).bind(repo).bind(label_set(labels))
+     .fetch_optional(conn)
+     .await?
+     .map(|(t,)| t - SYNC_OVERLAP);
+    Ok(last_updated.max(last_label_sync))
+}
+
+/// Record a successful sync that started at `started_at`, restricted to `labels` unless empty.
+/// A restricted sync doesn't cover the other issues, so it is kept apart from the repository
+pub async fn set_last_synced(conn: &mut Conn, repo: i64, labels: &[String], started_at: i64) -> Result<()> {
+    if labels.is_empty() {
+        sqlx::query(
+            "UPDATE repositories SET last_synced_at = ? WHERE id = ?"
+        ).bind(started_at).bind(repo)
+         .execute(conn)
+         .await?;
+    } else {
+        sqlx::query(
+            "INSERT INTO label_syncs (repo, labels, last_synced_at) VALUES (?, ?, ?)
+             ON CONFLICT (repo, labels) DO UPDATE SET last_synced_at=excluded.last_synced_at"
+        ).bind(repo).bind(label_set(labels)).bind(started_at)
+         .execute(conn)
+         .await?;
+    }
+    Ok(())
+}
+
+/// Insert or update an issue together with its labels.
+/// Returns false if the stored copy is newer than `issue` and was left untouched.
+pub async fn store_issue(conn: &mut Conn, repo: i64, issue: &IssueRecord, compress_bodies: bool) -> Result<bool> {
+    let stored_update = sqlx::query_as::<_, (i64,)>(
+        "SELECT updated_at FROM issues WHERE repo = ? AND number = ?"
+    ).bind(repo).bind(issue.number)
+     .fetch_optional(&mut *conn)
+     .await?;
+    if let Some((stored_update,)) = stored_update {
+        if stored_update > issue.updated_at { return Ok(false) }
+    }
+
+    let user = store_user(&mut *conn, repo, &issue.user_login, issue.user_avatar_url.as_deref()).await?;
+    if let Some(milestone) = &issue.milestone {
+        store_milestone(&mut *conn, repo, milestone).await?;
+    }
+    let (body, body_zstd) = db::encode_body(&issue.body, compress_bodies)?;
+    // Reopened issues lose their closing time
+    sqlx::query(
+        "INSERT INTO issues (repo, number, state, title, body, body_zstd, body_markdown, user_login, user, html_url,
+                             updated_at, created_at, reactions, comment_count, closed_at, milestone)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT (repo, number) DO UPDATE SET
+            state=excluded.state, title=excluded.title, body=excluded.body, body_zstd=excluded.body_zstd,
+            body_markdown=excluded.body_markdown,
+            user_login=excluded.user_login, user=excluded.user,
+            html_url=excluded.html_url, updated_at=excluded.updated_at,
+            created_at=coalesce(excluded.created_at, created_at),
+            reactions=coalesce(excluded.reactions, reactions),
+            comment_count=coalesce(excluded.comment_count, comment_count),
+            closed_at=CASE WHEN excluded.state = 2 THEN coalesce(excluded.closed_at, closed_at) END,
+            milestone=excluded.milestone"
+    ).bind(repo).bind(issue.number)
+     .bind(issue.state).bind(&issue.title).bind(body).bind(body_zstd).bind(issue.body_markdown)
+     .bind(&issue.user_login).bind(user).bind(&issue.html_url).bind(issue.updated_at)
+     .bind(issue.created_at).bind(issue.reactions).bind(issue.comment_count)
+     .bind(issue.closed_at).bind(issue.milestone.as_ref().map(|milestone| milestone.number))
+     .execute(&mut *conn)
+     .await?;
+
+    search::index_issue(&mut *conn, repo, issue.number, &issue.title, &issue.body, issue.body_markdown).await?;
+
+    sqlx::query(
+        "DELETE FROM is_labeled WHERE repo=? AND issue=?"
+    ).bind(repo).bind(issue.number)
+     .execute(&mut *conn)
+     .await?;
+
+    for label in &issue.labels {
+        store_label(&mut *conn, repo, label, None).await?;
+        sqlx::query(
+            "INSERT OR IGNORE INTO is_labeled (repo, issue, label)
+             VALUES (?, ?, (SELECT id FROM labels WHERE repo=? AND name=?))"
+        ).bind(repo).bind(issue.number)
+         .bind(repo).bind(label)
+         .execute(&mut *conn)
+         .await?;
+    }
+
+    sqlx::query(
+        "DELETE FROM is_assigned WHERE repo=? AND issue=?"
+    ).bind(repo).bind(issue.number)
+     .execute(&mut *conn)
+     .await?;
+
+    for assignee in &issue.assignees {
+        let user = store_user(&mut *conn, repo, assignee, None).await?;
+        sqlx::query(
+            "INSERT OR IGNORE INTO is_assigned (repo, issue, user) VALUES (?, ?, ?)"
+        ).bind(repo).bind(issue.number).bind(user)
+         .execute(&mut *conn)
+         .await?;
+    }
+
+    if let Some(comments) = &issue.comments {
+        sqlx::query(
+            "DELETE FROM comments WHERE repo=? AND issue=?"
+        ).bind(repo).bind(issue.number)
+         .execute(&mut *conn)
+         .await?;
+
+        for comment in comments {
+            let (body, body_zstd) = db::encode_body(&comment.body, compress_bodies)?;
+            sqlx::query(
+                "INSERT INTO comments (repo, issue, author, created_at, body, body_zstd, body_markdown)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)"
+            ).bind(repo).bind(issue.number)
+             .bind(&comment.author).bind(comment.created_at)
+             .bind(body).bind(body_zstd).bind(comment.body_markdown)
+             .execute(&mut *conn)
+             .await?;
+        }
+    }
+
+    Ok(true)
+}
+
+/// Insert or update the user `login` of the forge hosting `repo`, returning its id.
+/// A known avatar is kept if `avatar_url` is missing
+pub async fn store_user(conn: &mut Conn, repo: i64, login: &str, avatar_url: Option<&str>) -> Result<i64> {
+    let (backend, base_url) = backend(&mut *conn, repo).await?;
+    let html_url = backend.user_url(&base_url, login).into_string();
+
+    sqlx::query(
+        "INSERT INTO users (login, html_url, avatar_url) VALUES (?, ?, ?)
+         ON CONFLICT (html_url, login) DO UPDATE SET
+            avatar_url=coalesce(excluded.avatar_url, avatar_url)"
+    ).bind(login).bind(&html_url).bind(avatar_url)
+     .execute(&mut *conn)
+     .await?;
+
+    sqlx::query_as::<_, (i64,)>(
+        "SELECT id FROM users WHERE html_url = ? AND login = ?"
+    ).bind(&html_url).bind(login)
+     .fetch_all(conn)
+     .await?
+     .pop()
+     .map(|(id,)| id)
+     .with_context(|| format!("Couldn't find user '{}' in database", login))
+}
+
+/// Treat label `alias` as `label` from now on
+pub async fn add_label_alias(conn: &mut Conn, alias: &str, label: &str) -> Result<()> {
+    if alias == label {
+        return Err(anyhow!("Label {} can't be an alias of itself", label));
+    }
+    // Only one level of aliases is resolved
+    let (chained,) = sqlx::query_as::<_, (i64,)>(
+        "SELECT count(*) FROM label_aliases WHERE alias = ? OR label = ?"
+    ).bind(label).bind(alias)
+     .fetch_one(&mut *conn)
+     .await?;
+    if chained > 0 {
+        return Err(anyhow!("Aliases can't be chained, {} or {} is already part of an alias", alias, label));
+    }
+
+    sqlx::query(
+        "INSERT INTO label_aliases (alias, label) VALUES (?, ?)
+         ON CONFLICT (alias) DO UPDATE SET label=excluded.label"
+    ).bind(alias).bind(label)
+     .execute(conn)
+     .await?;
+    Ok(())
+}
+
+/// Returns false if `alias` wasn't known
+pub async fn remove_label_alias(conn: &mut Conn, alias: &str) -> Result<bool> {
+    Ok(sqlx::query(
+        "DELETE FROM label_aliases WHERE alias = ?"
+    ).bind(alias)
+     .execute(conn)
+     .await? > 0)
+}
+
+pub async fn label_aliases(conn: &mut Conn) -> Result<Vec<(String, String)>> {
+    Ok(sqlx::query_as(
+        "SELECT alias, label FROM label_aliases ORDER BY label, alias"
+    ).fetch_all(conn)
+     .await?)
+}
+
+/// Link issues stored before the users table existed to their authors
+pub async fn link_users(conn: &mut Conn) -> Result<()> {
+    let authors = sqlx::query_as::<_, (i64, String)>(
+        "SELECT DISTINCT repo, user_login FROM issues WHERE user IS NULL"
+    ).fetch_all(&mut *conn)
+     .await?;
+
+    for (repo, login) in authors {
+        let user = store_user(&mut *conn, repo, &login, None).await?;
+        sqlx::query(
+            "UPDATE issues SET user = ? WHERE repo = ? AND user_login = ?"
+        ).bind(user).bind(repo).bind(&login)
+         .execute(&mut *conn)
+         .await?;
+    }
+    Ok(())
+}
+
+/// Insert a label if it's unknown. A known color is kept if `color` is missing
+pub async fn store_label(conn: &mut Conn, repo: i64, name: &str, color: Option<&str>) -> Result<()> {
+    let color = color.map(|color| color.trim_start_matches('#'));
+    sqlx::query(
+        "INSERT INTO labels (repo, name, color) VALUES (?, ?, ?)
+         ON CONFLICT (repo, name) DO UPDATE SET color=coalesce(excluded.color, color)"
+    ).bind(repo).bind(name).bind(color)
+     .execute(conn)
+     .await?;
+    Ok(())
+}
+
+/// Insert or update a milestone of `repo`
+pub async fn store_milestone(conn: &mut Conn, repo: i64, milestone: &MilestoneRecord) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO milestones (repo, number, title, html_url, due_on, closed) VALUES (?, ?, ?, ?, ?, ?)
+         ON CONFLICT (repo, number) DO UPDATE SET
+            title=excluded.title, html_url=excluded.html_url, due_on=excluded.due_on, closed=excluded.closed"
+    ).bind(repo).bind(milestone.number)
+     .bind(&milestone.title).bind(&milestone.html_url).bind(milestone.due_on).bind(milestone.closed)
+     .execute(conn)
+     .await?;
+    Ok(())
+}
+
+/// Move `repo` to its new owner/name and rewrite the stored issue URLs.
+/// If the new name is already known, the old entry is dropped in its favour.
+/// Returns the id under which the repository is stored afterwards.
+pub async fn rename_repository(conn: &mut Conn, repo: i64, (old_owner, old_name): (&str, &str), (new_owner, new_name): (&str, &str)) -> Result<i64> {
+    if let Some(existing) = find_repo_id(&mut *conn, new_owner, new_name).await? {
+        remove_repository(conn, repo).await?;
+        return Ok(existing)
+    }
+
+    // tag: entry ids keep the name of the repository before its first rename
+    sqlx::query(
+        "UPDATE repositories SET owner = ?, name = ?, tag_name = coalesce(tag_name, ?) WHERE id = ?"
+    ).bind(new_owner).bind(new_name).bind(format!("{}/{}", old_owner, old_name)).bind(repo)
+     .execute(&mut *conn)
+     .await?;
+
+    sqlx::query(
+        "UPDATE issues SET html_url = replace(html_url, ?, ?) WHERE repo = ?;
+         UPDATE milestones SET html_url = replace(html_url, ?, ?) WHERE repo = ?"
+    ).bind(format!("/{}/{}/", old_owner, old_name))
+     .bind(format!("/{}/{}/", new_owner, new_name))
+     .bind(repo)
+     .bind(format!("/{}/{}/", old_owner, old_name))
+     .bind(format!("/{}/{}/", new_owner, new_name))
+     .bind(repo)
+     .execute(&mut *conn)
+     .await?;
+
+    Ok(repo)
+}
+
+/// Delete a repository with all of its issues and labels
+pub async fn remove_repository(conn: &mut Conn, repo: i64) -> Result<()> {
+    sqlx::query(
+        "DELETE FROM label_syncs WHERE repo = ?;
+         DELETE FROM is_labeled WHERE repo = ?;
+         DELETE FROM is_assigned WHERE repo = ?;
+         DELETE FROM comments WHERE repo = ?;
+         DELETE FROM issues WHERE repo = ?;
+         DELETE FROM milestones WHERE repo = ?;
+         DELETE FROM labels WHERE repo = ?;
+         DELETE FROM repositories WHERE id = ?"
+    ).bind(repo).bind(repo).bind(repo).bind(repo).bind(repo).bind(repo).bind(repo).bind(repo)
+     .execute(conn)
+     .await?;
+    Ok(())
+}
+
+/// Remember which forge a repository was synchronised from
+pub async fn set_backend(conn: &mut Conn, repo: i64, backend: Backend, base_url: &Url) -> Result<()> {
+    sqlx::query(
+        "UPDATE repositories SET backend = ?, base_url = ? WHERE id = ?"
+    ).bind(backend.as_str()).bind(base_url.as_str()).bind(repo)
+     .execute(conn)
+     .await?;
+    Ok(())
+}
+
+pub async fn backend(conn: &mut Conn, repo: i64) -> Result<(Backend, Url)> {
+    let (backend, base_url) = sqlx::query_as::<_, (String, Option<String>)>(
+        "SELECT backend, base_url FROM repositories WHERE id = ?"
+    ).bind(repo)
+     .fetch_one(conn)
+     .await
+     .with_context(|| format!("Couldn't find backend of repo id {}", repo))?;
+
+    let backend: Backend = backend.parse()?;
+    let base_url = match base_url {
+        Some(url) => Url::parse(&url)?,
+        None => backend.default_base_url()
+    };
+    Ok((backend, base_url))
+}
+
+/// owner/name of `repo` in tag: entry ids, frozen at the first rename
+pub async fn tag_name(conn: &mut Conn, repo: i64) -> Result<String> {
+    sqlx::query_as::<_, (String,)>(
+        "SELECT coalesce(tag_name, owner || '/' || name) FROM repositories WHERE id = ?"
+    ).bind(repo)
+     .fetch_one(conn)
+     .await
+     .map(|(tag_name,)| tag_name)
+     .with_context(|| format!("Couldn't find repo id {}", repo))
+}
+
 pub async fn list_repositories(db: &mut Conn) -> sqlx::Result<Vec<RepositoryInfo>> {
     sqlx::query_as(
         "SELECT repositories.owner, repositories.name,
@@ -50,30 +468,21 @@ pub mod graphql {
     use std::time::Duration;
     use reqwest::header;
     use serde::Serialize;
-    use futures_retry::{ ErrorHandler, RetryPolicy, FutureRetry };
+    use futures_retry::FutureRetry;
     use graphql_client::QueryBody;
 
-    static API_ENDPOINT: &str = "https://api.github.com/graphql";
-    static USER_AGENT: &str = "github.com/tilpner/github-label-feed";
-
-    static RETRY_DELAY: &[u64] = &[ 5, 50, 250, 1000, 5000, 25000 ];
+    use super::{ USER_AGENT, RetryStrategy };
 
-    pub struct RetryStrategy;
-    impl ErrorHandler<reqwest::Error> for RetryStrategy {
-        type OutError = reqwest::Error;
+    static API_ENDPOINT: &str = "https://api.github.com/graphql";
 
-        fn handle(&mut self, attempt: usize, e: reqwest::Error) -> RetryPolicy<Self::OutError> {
-            match RETRY_DELAY.get(attempt) {
-                Some(&ms) => RetryPolicy::WaitRetry(Duration::from_millis(ms)),
-                None => RetryPolicy::ForwardError(e)
-            }
-        }
+    pub async fn query(client: &reqwest::Client, api_token: &str, query: QueryBody<impl Serialize>) -> reqwest::Result<reqwest::Response> {
+        query_endpoint(client, API_ENDPOINT, api_token, query).await
     }
 
-    pub async fn query(client: &reqwest::Client, api_token: &str, query: QueryBody<impl Serialize>) -> reqwest::Result<reqwest::Response> {
+    pub async fn query_endpoint(client: &reqwest::Client, endpoint: &str, api_token: &str, query: QueryBody<impl Serialize>) -> reqwest::Result<reqwest::Response> {
         FutureRetry::new(|| {
             client
-                .post(API_ENDPOINT)
+                .post(endpoint)
                 .timeout(Duration::from_secs(60))
                 .header(header::USER_AGENT, USER_AGENT)
                 .bearer_auth(api_token)
@@ -85,3 +494,73 @@ pub mod graphql {
             .map_err(|(e, _)| e)
     }
 }
+
+pub mod rest {
+    use std::time::Duration;
+    use reqwest::{ header, StatusCode };
+    use futures_retry::FutureRetry;
+    use url::Url;
+    use chrono::{ DateTime, Utc };
+    use tracing::info;
+
+    use super::{ USER_AGENT, RETRY_DELAY, RetryStrategy };
+
+    /// Longest wait for a Retry-After header, rather than stalling the sync for hours
+    static MAX_RETRY_AFTER: Duration = Duration::from_secs(600);
+
+    pub enum Auth {
+        None,
+        Bearer(String),
+        Basic(String, String)
+    }
+
+    impl Auth {
+        pub fn bearer(token: Option<&String>) -> Self {
+            token.cloned().map(Auth::Bearer).unwrap_or(Auth::None)
+        }
+    }
+
+    /// Wait requested by the Retry-After header of `response`, in seconds or as HTTP date
+    fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+        let value = response.headers().get(header::RETRY_AFTER)?.to_str().ok()?;
+        let delay = match value.trim().parse::<u64>() {
+            Ok(seconds) => Duration::from_secs(seconds),
+            Err(_) => (DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc) - Utc::now())
+                .to_std()
+                .unwrap_or_default()
+        };
+        Some(delay.min(MAX_RETRY_AFTER))
+    }
+
+    /// GET `url`, retrying connection failures, server errors and rate limiting with backoff
+    pub async fn get(client: &reqwest::Client, auth: &Auth, url: &Url) -> reqwest::Result<reqwest::Response> {
+        let mut attempt = 0;
+        loop {
+            let response = FutureRetry::new(|| {
+                let request = client
+                    .get(url.clone())
+                    .timeout(Duration::from_secs(60))
+                    .header(header::USER_AGENT, USER_AGENT);
+                match auth {
+                    Auth::None => request,
+                    Auth::Bearer(token) => request.bearer_auth(token),
+                    Auth::Basic(user, password) => request.basic_auth(user, Some(password))
+                }.send()
+            }, RetryStrategy)
+                .await
+                .map(|(res, _)| res)
+                .map_err(|(e, _)| e)?;
+
+            let status = response.status();
+            match RETRY_DELAY.get(attempt) {
+                Some(&ms) if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS => {
+                    let delay = retry_after(&response).unwrap_or_else(|| Duration::from_millis(ms));
+                    info!("{} responded {}, retrying in {:?}", url, status, delay);
+                    smol::Timer::after(delay).await;
+                    attempt += 1;
+                },
+                _ => return response.error_for_status()
+            }
+        }
+    }
+}
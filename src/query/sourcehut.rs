@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+
+use graphql_client::{ QueryBody, Response };
+use reqwest::Client;
+use serde::{ Serialize, Deserialize };
+use url::Url;
+
+use tracing::{ error, info, debug };
+
+use crate::{ Conn, query::{ *, issues::IssueState } };
+
+// https://man.sr.ht/todo.sr.ht/graphql.md
+// Tickets can't be filtered by update time, so every sync pages through the
+// whole tracker and only stores tickets updated after their stored version.
+
+static LABELS_QUERY: &str = r#"
+query Labels($owner: String!, $name: String!, $cursor: Cursor) {
+  user(username: $owner) {
+    tracker(name: $name) {
+      labels(cursor: $cursor) {
+        results { name backgroundColor }
+        cursor
+      }
+    }
+  }
+}"#;
+
+static TICKETS_QUERY: &str = r#"
+query Tickets($owner: String!, $name: String!, $cursor: Cursor) {
+  user(username: $owner) {
+    tracker(name: $name) {
+      tickets(cursor: $cursor) {
+        results {
+          id
+          subject
+          body
+          status
+          created
+          updated
+          submitter { canonicalName }
+          assignees { canonicalName }
+          labels { name }
+        }
+        cursor
+      }
+    }
+  }
+}"#;
+
+#[derive(Serialize)]
+struct Variables<'a> {
+    owner: &'a str,
+    name: &'a str,
+    cursor: Option<String>
+}
+
+#[derive(Deserialize)]
+struct Data<T> {
+    user: Option<User<T>>
+}
+
+#[derive(Deserialize)]
+struct User<T> {
+    tracker: Option<T>
+}
+
+#[derive(Deserialize)]
+struct Page<T> {
+    results: Vec<T>,
+    cursor: Option<String>
+}
+
+#[derive(Deserialize)]
+struct LabelsTracker {
+    labels: Page<Label>
+}
+
+#[derive(Deserialize)]
+struct TicketsTracker {
+    tickets: Page<Ticket>
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Label {
+    name: String,
+    background_color: Option<String>
+}
+
+#[derive(Deserialize)]
+struct Ticket {
+    id: i64,
+    subject: String,
+    body: Option<String>,
+    status: String,
+    created: String,
+    updated: String,
+    submitter: Entity,
+    #[serde(default)]
+    assignees: Vec<Entity>,
+    #[serde(default)]
+    labels: Vec<Label>
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Entity {
+    canonical_name: String
+}
+
+async fn fetch_page<T: serde::de::DeserializeOwned>(client: &Client, endpoint: &str, api_token: &str,
+        query: &'static str, operation_name: &'static str, variables: Variables<'_>) -> anyhow::Result<T> {
+    let res = graphql::query_endpoint(client, endpoint, api_token, QueryBody {
+        variables, query, operation_name
+    }).await?;
+    let response: Response<Data<T>> = res.json().await?;
+
+    for error in response.errors.unwrap_or_default() {
+        error!("{:?}", error);
+    }
+
+    response.data
+        .and_then(|data| data.user)
+        .and_then(|user| user.tracker)
+        .ok_or_else(|| anyhow::anyhow!("Missing tracker"))
+}
+
+pub async fn update(mut conn: &mut Conn, base_url: &Url, api_token: &str, (ref owner, ref name): (String, String), compress_bodies: bool) -> anyhow::Result<()> {
+    let repo = repo_id(&mut conn, owner, name).await?;
+    let client = Client::new();
+    let endpoint = base_url.join("/query")?;
+    let username = owner.trim_start_matches('~');
+    info!("updating tracker {}/{} ({})", owner, name, repo);
+
+    let mut cursor = None;
+    loop {
+        let tracker: LabelsTracker = fetch_page(&client, endpoint.as_str(), api_token, LABELS_QUERY, "Labels",
+            Variables { owner: username, name, cursor }).await?;
+        for label in tracker.labels.results {
+            debug!("{}: {}", repo, label.name);
+            store_label(&mut conn, repo, &label.name, label.background_color.as_deref()).await?;
+        }
+        cursor = tracker.labels.cursor;
+        if cursor.is_none() { break }
+    }
+
+    let stored: HashMap<i64, i64> = sqlx::query_as::<_, (i64, i64)>(
+        "SELECT number, updated_at FROM issues WHERE repo=?"
+    ).bind(repo)
+     .fetch_all(&mut *conn)
+     .await?
+     .into_iter()
+     .collect();
+
+    let mut cursor = None;
+    loop {
+        let tracker: TicketsTracker = fetch_page(&client, endpoint.as_str(), api_token, TICKETS_QUERY, "Tickets",
+            Variables { owner: username, name, cursor }).await?;
+        for ticket in tracker.tickets.results {
+            debug!("#{}: {}", ticket.id, ticket.subject);
+            let state = match &ticket.status[..] {
+                "REPORTED" | "CONFIRMED" | "IN_PROGRESS" | "PENDING" => IssueState::OPEN,
+                "RESOLVED" => IssueState::CLOSED,
+                other => IssueState::Other(other.to_owned())
+            };
+            let ts = chrono::DateTime::parse_from_rfc3339(&ticket.updated)?
+                .timestamp();
+            if stored.get(&ticket.id).map_or(false, |&updated_at| ts <= updated_at) {
+                continue
+            }
+
+            let mut html_url = base_url.clone();
+            html_url.path_segments_mut()
+                .expect("base url cannot be a base")
+                .pop_if_empty()
+                .push(owner).push(name).push(&ticket.id.to_string());
+
+            store_issue(&mut conn, repo, &IssueRecord {
+                number: ticket.id,
+                state: state.to_integer(),
+                title: ticket.subject,
+                body: ticket.body.unwrap_or_default(),
+                body_markdown: true,
+                user_login: ticket.submitter.canonical_name,
+                user_avatar_url: None,
+                html_url: html_url.into_string(),
+                updated_at: ts,
+                created_at: Some(chrono::DateTime::parse_from_rfc3339(&ticket.created)?.timestamp()),
+                reactions: None,
+                comment_count: None,
+                labels: ticket.labels.into_iter().map(|l| l.name).collect(),
+                assignees: ticket.assignees.into_iter().map(|a| a.canonical_name).collect(),
+                comments: None,
+                closed_at: None,
+                milestone: None
+            }, compress_bodies).await?;
+        }
+        cursor = tracker.tickets.cursor;
+        debug!("has_next_page: {}", cursor.is_some());
+        if cursor.is_none() { break }
+    }
+
+    Ok(())
+}
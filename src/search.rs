@@ -0,0 +1,260 @@
+use std::collections::BTreeSet;
+
+use sqlx::prelude::*;
+use anyhow::{ anyhow, Result, Context };
+use tracing::info;
+
+use crate::{ Conn, db, query };
+
+/// Words of `text` as stored in the term index: lowercased runs of alphanumeric
+/// characters, outside of tags if `text` is HTML, so rendered and plain bodies index alike
+pub fn terms(text: &str, html: bool) -> BTreeSet<String> {
+    let mut terms = BTreeSet::new();
+    let mut word = String::new();
+    let mut in_tag = false;
+
+    for c in text.chars().chain(std::iter::once(' ')) {
+        match c {
+            '<' if html => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag && c.is_alphanumeric() => word.extend(c.to_lowercase()),
+            _ => ()
+        }
+        if !c.is_alphanumeric() && !word.is_empty() {
+            terms.insert(std::mem::take(&mut word));
+        }
+    }
+
+    terms
+}
+
+/// Condition of a text query, as in GitHub's issue search
+#[derive(Debug, PartialEq)]
+pub enum Qualifier {
+    /// Word of the title or body, as indexed
+    Term(String),
+    /// is:open, is:closed or state:…, as stored state
+    State(i64),
+    /// label:a,b, carrying any of the labels
+    Label(Vec<String>),
+    /// milestone:title
+    Milestone(String),
+    Author(String),
+    Assignee(String),
+    NoLabel,
+    NoMilestone,
+    NoAssignee
+}
+
+/// Words of `text`, split at whitespace outside of double quotes, which are removed.
+/// Words quoted as a whole are marked, as they are searched for even if they contain a colon
+fn words(text: &str) -> Vec<(String, bool)> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let (mut quoted, mut in_quotes) = (false, false);
+    for c in text.chars().chain(std::iter::once(' ')) {
+        match c {
+            '"' => {
+                quoted |= word.is_empty() && !in_quotes;
+                in_quotes = !in_quotes;
+            },
+            c if c.is_whitespace() && !in_quotes => {
+                if !word.is_empty() || quoted {
+                    words.push((std::mem::take(&mut word), quoted));
+                }
+                quoted = false;
+            },
+            c => word.push(c)
+        }
+    }
+    words
+}
+
+fn parse_qualifier(key: &str, value: &str) -> Result<Option<Qualifier>> {
+    let state = |name: &str| match query::issues::IssueState::from_name(name).to_integer() {
+        0 => Err(anyhow!("Invalid state {:?}, expected open or closed", name)),
+        state => Ok(Qualifier::State(state))
+    };
+    Ok(Some(match (key, value) {
+        // Only issues are stored
+        ("is", "issue") | ("type", "issue") => return Ok(None),
+        ("is", "pr") | ("type", "pr") => return Err(anyhow!("Pull requests aren't stored, {}:{} matches nothing", key, value)),
+        ("is", state_name) | ("state", state_name) => state(state_name)?,
+        ("label", labels) => Qualifier::Label(labels.split(',').map(str::to_owned).collect()),
+        ("milestone", title) => Qualifier::Milestone(title.to_owned()),
+        ("author", login) => Qualifier::Author(login.to_owned()),
+        ("assignee", login) => Qualifier::Assignee(login.to_owned()),
+        ("no", "label") => Qualifier::NoLabel,
+        ("no", "milestone") => Qualifier::NoMilestone,
+        ("no", "assignee") => Qualifier::NoAssignee,
+        _ => return Err(anyhow!("Unsupported qualifier {}:{}, quote it to search for the words", key, value))
+    }))
+}
+
+/// Alternatives of the text query `text`, separated by OR, each requiring all of its conditions and
+/// excluding those prefixed with -. Conditions are words, searched like indexed text so that they match
+/// regardless of case and `segfault` matches `SegFault`, or GitHub search qualifiers like `label:bug`
+pub fn parse_query(text: &str) -> Result<Vec<Vec<(bool, Qualifier)>>> {
+    let mut alternatives = Vec::new();
+    let mut alternative = Vec::new();
+    // is:issue adds no condition, but explicitly selects every issue
+    let mut all_issues = false;
+    // Words without letters or digits like ??? add no condition either, and would select every issue
+    let complete = |alternative: &Vec<_>, all_issues: bool| if alternative.is_empty() && !all_issues {
+        Err(anyhow!("Invalid query {:?}, every alternative needs a word with letters or digits, or a qualifier", text))
+    } else { Ok(()) };
+    for (word, quoted) in words(text) {
+        if word == "OR" && !quoted {
+            complete(&alternative, all_issues)?;
+            alternatives.push(std::mem::take(&mut alternative));
+            all_issues = false;
+            continue
+        }
+        let (include, word) = if word.starts_with('-') && !quoted {
+            (false, &word[1..])
+        } else {
+            (true, &word[..])
+        };
+        let mut parts = word.splitn(2, ':');
+        match (parts.next(), parts.next()) {
+            (Some(key), Some(value)) if !quoted && key.chars().all(|c| c.is_ascii_alphabetic()) => {
+                match parse_qualifier(key, value).with_context(|| format!("Invalid query {:?}", text))? {
+                    Some(qualifier) => alternative.push((include, qualifier)),
+                    None => all_issues |= include
+                }
+            },
+            _ => alternative.extend(terms(word, false).into_iter().map(|term| (include, Qualifier::Term(term))))
+        }
+    }
+
+    complete(&alternative, all_issues)?;
+    alternatives.push(alternative);
+    Ok(alternatives)
+}
+
+/// Replace the indexed terms of one issue, whose body is HTML unless `body_markdown` is set
+pub async fn index_issue(conn: &mut Conn, repo: i64, number: i64, title: &str, body: &str, body_markdown: bool) -> Result<()> {
+    sqlx::query(
+        "DELETE FROM issue_terms WHERE repo = ? AND issue = ?"
+    ).bind(repo).bind(number)
+     .execute(&mut *conn)
+     .await?;
+
+    let mut words = terms(title, false);
+    words.extend(terms(body, !body_markdown));
+    for term in words {
+        sqlx::query(
+            "INSERT INTO issue_terms (repo, issue, term) VALUES (?, ?, ?)"
+        ).bind(repo).bind(number).bind(term)
+         .execute(&mut *conn)
+         .await?;
+    }
+    Ok(())
+}
+
+/// Index every stored issue, for databases that predate the term index
+pub async fn reindex(conn: &mut Conn) -> Result<()> {
+    let issues: Vec<(i64, i64, String, String, Option<Vec<u8>>, bool)> = sqlx::query_as(
+        "SELECT repo, number, title, body, body_zstd, body_markdown FROM issues"
+    ).fetch_all(&mut *conn)
+     .await?;
+
+    info!("indexing {} issues", issues.len());
+    for (repo, number, title, body, body_zstd, body_markdown) in issues {
+        let body = db::decode_body(body, body_zstd)?;
+        index_issue(&mut *conn, repo, number, &title, &body, body_markdown).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(words: &[&str]) -> BTreeSet<String> {
+        words.iter().map(|&word| word.to_owned()).collect()
+    }
+
+    fn term(word: &str) -> Qualifier {
+        Qualifier::Term(word.to_owned())
+    }
+
+    #[test]
+    fn query_words() {
+        assert_eq!(parse_query("Panic  SEGFAULT").unwrap(), vec![ vec![ (true, term("panic")), (true, term("segfault")) ] ]);
+        assert_eq!(parse_query("foo-bar").unwrap(), vec![ vec![ (true, term("bar")), (true, term("foo")) ] ]);
+        assert_eq!(parse_query("or and").unwrap(), vec![ vec![ (true, term("or")), (true, term("and")) ] ]);
+    }
+
+    #[test]
+    fn query_negation() {
+        assert_eq!(parse_query("crash -windows").unwrap(), vec![ vec![ (true, term("crash")), (false, term("windows")) ] ]);
+        assert_eq!(parse_query("-author:bot").unwrap(), vec![ vec![ (false, Qualifier::Author(String::from("bot"))) ] ]);
+        // Quoted words are searched for as written
+        assert_eq!(parse_query(r#""-windows""#).unwrap(), vec![ vec![ (true, term("windows")) ] ]);
+    }
+
+    #[test]
+    fn query_quoted_colons() {
+        assert_eq!(parse_query(r#""error: oops""#).unwrap(), vec![ vec![ (true, term("error")), (true, term("oops")) ] ]);
+        assert_eq!(parse_query(r#""http://a""#).unwrap(), vec![ vec![ (true, term("a")), (true, term("http")) ] ]);
+        assert!(parse_query("http://a").is_err());
+        assert!(parse_query(":foo").is_err());
+    }
+
+    #[test]
+    fn query_states() {
+        assert_eq!(parse_query("is:open").unwrap(), vec![ vec![ (true, Qualifier::State(1)) ] ]);
+        assert_eq!(parse_query("state:closed").unwrap(), vec![ vec![ (true, Qualifier::State(2)) ] ]);
+        assert!(parse_query("is:merged").is_err());
+        assert!(parse_query("is:pr").is_err());
+        assert!(parse_query("type:pr").is_err());
+    }
+
+    #[test]
+    fn query_qualifiers() {
+        assert_eq!(parse_query("label:bug,c++ milestone:v2.0").unwrap(), vec![ vec![
+            (true, Qualifier::Label(vec![ String::from("bug"), String::from("c++") ])),
+            (true, Qualifier::Milestone(String::from("v2.0")))
+        ] ]);
+        assert_eq!(parse_query(r#"milestone:"v2 beta""#).unwrap(), vec![ vec![ (true, Qualifier::Milestone(String::from("v2 beta"))) ] ]);
+        assert_eq!(parse_query("assignee:foo no:label -no:milestone no:assignee").unwrap(), vec![ vec![
+            (true, Qualifier::Assignee(String::from("foo"))),
+            (true, Qualifier::NoLabel),
+            (false, Qualifier::NoMilestone),
+            (true, Qualifier::NoAssignee)
+        ] ]);
+        assert!(parse_query("no:reviewer").is_err());
+        assert!(parse_query("sort:created").is_err());
+    }
+
+    #[test]
+    fn query_alternatives() {
+        assert_eq!(parse_query("panic OR segfault -linux").unwrap(), vec![
+            vec![ (true, term("panic")) ],
+            vec![ (true, term("segfault")), (false, term("linux")) ]
+        ]);
+        assert_eq!(parse_query(r#"a "OR" b"#).unwrap(), vec![ vec![ (true, term("a")), (true, term("or")), (true, term("b")) ] ]);
+    }
+
+    #[test]
+    fn query_empty_alternatives() {
+        assert!(parse_query("").is_err());
+        assert!(parse_query("???").is_err());
+        assert!(parse_query(r#""""#).is_err());
+        assert!(parse_query("panic OR").is_err());
+        assert!(parse_query("OR panic").is_err());
+        assert!(parse_query("panic OR ??? OR segfault").is_err());
+        assert!(parse_query("-is:issue").is_err());
+        assert!(parse_query("type:issue OR -is:issue").is_err());
+        // is:issue adds no condition, but selects every issue on purpose
+        assert_eq!(parse_query("is:issue").unwrap(), vec![ vec![] ]);
+        assert_eq!(parse_query("panic OR type:issue").unwrap(), vec![ vec![ (true, term("panic")) ], vec![] ]);
+    }
+
+    #[test]
+    fn strips_tags_of_html_only() {
+        assert_eq!(terms("<p>Vec&lt;u8&gt; is <em>slow</em></p>", true), set(&[ "gt", "is", "lt", "slow", "u8", "vec" ]));
+        assert_eq!(terms("`Vec<u8>` is slow", false), set(&[ "is", "slow", "u8", "vec" ]));
+    }
+}
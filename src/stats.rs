@@ -0,0 +1,77 @@
+use sqlx::prelude::*;
+use anyhow::{ anyhow, Result };
+use chrono::{ Utc, TimeZone };
+
+use crate::{
+    parse_repo,
+    Conn,
+    query::{ find_repo_id, issues::IssueState }
+};
+
+async fn print_repository(conn: &mut Conn, repo: i64, owner: &str, name: &str) -> Result<()> {
+    println!("{}/{}", owner, name);
+
+    let (open, closed) = sqlx::query_as::<_, (i64, i64)>(
+        "SELECT count(CASE WHEN state = ? THEN 1 END), count(CASE WHEN state = ? THEN 1 END)
+         FROM issues WHERE repo = ?"
+    ).bind(IssueState::OPEN.to_integer()).bind(IssueState::CLOSED.to_integer()).bind(repo)
+     .fetch_one(&mut *conn)
+     .await?;
+    println!("  issues: {} open, {} closed", open, closed);
+
+    let latest = sqlx::query_as::<_, (i64, String, i64)>(
+        "SELECT number, title, updated_at FROM issues WHERE repo = ?
+         ORDER BY updated_at DESC LIMIT 1"
+    ).bind(repo)
+     .fetch_optional(&mut *conn)
+     .await?;
+    if let Some((number, title, updated_at)) = latest {
+        println!("  last update: {} #{} {}", Utc.timestamp(updated_at, 0).to_rfc3339(), number, title);
+    }
+
+    let labels = sqlx::query_as::<_, (String, i64)>(
+        "SELECT labels.name, count(is_labeled.issue) FROM labels
+         LEFT JOIN is_labeled ON is_labeled.label = labels.id
+         WHERE labels.repo = ?
+         GROUP BY labels.id
+         ORDER BY count(is_labeled.issue) DESC, labels.name"
+    ).bind(repo)
+     .fetch_all(&mut *conn)
+     .await?;
+    println!("  labels: {}", labels.len());
+    for (label, count) in labels {
+        println!("    {}: {}", label, count);
+    }
+
+    Ok(())
+}
+
+/// Print statistics of `repo`, or of every repository, and the database size
+pub async fn run(conn: &mut Conn, repo: Option<String>) -> Result<()> {
+    let repos = match repo {
+        Some(repo) => {
+            let (owner, name) = parse_repo(&repo)?;
+            let id = find_repo_id(&mut *conn, &owner, &name).await?
+                .ok_or_else(|| anyhow!("Unknown repository {}", repo))?;
+            vec![ (id, owner, name) ]
+        },
+        None => sqlx::query_as::<_, (i64, String, String)>(
+            "SELECT id, owner, name FROM repositories ORDER BY owner, name"
+        ).fetch_all(&mut *conn)
+         .await?
+    };
+
+    for (id, owner, name) in repos {
+        print_repository(&mut *conn, id, &owner, &name).await?;
+    }
+
+    let (pages,) = sqlx::query_as::<_, (i64,)>("PRAGMA page_count")
+        .fetch_one(&mut *conn)
+        .await?;
+    let (page_size,) = sqlx::query_as::<_, (i64,)>("PRAGMA page_size")
+        .fetch_one(&mut *conn)
+        .await?;
+    println!("database size: {} KiB", pages * page_size / 1024);
+
+    Ok(())
+}